@@ -0,0 +1,7 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// rebuild when migrations change, as these are embedded by sqlx::migrate!
+fn main() {
+    println!("cargo:rerun-if-changed=../migrations");
+}
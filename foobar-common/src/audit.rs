@@ -0,0 +1,39 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Audit log of administrative actions
+//!
+//! Audit events are regular `tracing` events at info level, with
+//! a dedicated target and a fixed set of fields, so they reach all
+//! configured log sinks, and may additionally be routed into
+//! a separate one by filtering with [`is_audit_event`].
+
+use tracing::Metadata;
+
+/// Target of all audit events
+pub const AUDIT_TARGET: &str = "audit";
+
+/// Emit audit event for an administrative action
+///
+/// `actor` identifies who performed the action, as established by
+/// authentication, `target` is the object acted upon, and `result`
+/// is the outcome, `success` or `failure`. All values are recorded
+/// using their `Display` implementation.
+#[macro_export]
+macro_rules! audit {
+    (actor = $actor:expr, action = $action:expr, target = $target:expr, result = $result:expr $(,)?) => {
+        ::tracing::info!(
+            target: $crate::audit::AUDIT_TARGET,
+            actor = %$actor,
+            action = %$action,
+            target = %$target,
+            result = %$result,
+            "audit"
+        )
+    };
+}
+
+/// Check whether event is an audit event, for use with `filter_fn`
+pub fn is_audit_event(meta: &Metadata<'_>) -> bool {
+    meta.target() == AUDIT_TARGET
+}
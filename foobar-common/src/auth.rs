@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use sha2::{Digest, Sha256};
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Check whether `Authorization` header value carries given bearer token
+///
+/// Comparison is done in constant time to not leak the token
+/// through response timing.
+pub fn check_bearer_token(authorization: Option<&str>, token: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| constant_time_eq(value.as_bytes(), token.as_bytes()))
+}
+
+/// Check whether `Authorization` header value carries given basic credentials
+///
+/// `credentials` is base64 encoded `user:password` pair, the same
+/// as it appears in the header, so no decoding is needed and the
+/// comparison is done in constant time.
+pub fn check_basic_auth(authorization: Option<&str>, credentials: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Basic "))
+        .is_some_and(|value| constant_time_eq(value.as_bytes(), credentials.as_bytes()))
+}
+
+/// Hash API key for storing in configuration
+///
+/// Result is lowercase hex encoded SHA-256 digest, same as produced
+/// by `sha256sum`.
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Check whether API key matches any of given key hashes
+///
+/// Hashes must be in format produced by [`hash_api_key`]. All hashes
+/// are compared in constant time, so neither the key nor the number
+/// of matching hashes leaks through response timing.
+pub fn check_api_key(key: &str, hashes: &[String]) -> bool {
+    let hash = hash_api_key(key);
+    hashes.iter().fold(false, |found, candidate| {
+        constant_time_eq(hash.as_bytes(), candidate.as_bytes()) | found
+    })
+}
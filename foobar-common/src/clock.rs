@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Source of current time
+//!
+//! Time-dependent logic takes a clock instead of querying system
+//! time directly, so tests may drive it deterministically with
+//! [`MockClock`].
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub trait Clock: Send + Sync {
+    /// Current wall clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time, for measuring intervals
+    fn instant(&self) -> Instant;
+
+    /// Wait for given duration
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Clock backed by system time and `tokio` timers
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl SystemClock {
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// Clock which only moves when advanced explicitly
+///
+/// Sleeping advances the clock by the requested duration and
+/// completes immediately.
+pub struct MockClock {
+    base_instant: Instant,
+    state: Mutex<(DateTime<Utc>, Duration)>,
+}
+
+impl MockClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            base_instant: Instant::now(),
+            state: Mutex::new((now, Duration::ZERO)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self
+            .state
+            .lock()
+            .expect("clock lock should not be poisoned");
+        state.0 += duration;
+        state.1 += duration;
+    }
+
+    /// Total time the clock was advanced by since creation
+    pub fn elapsed(&self) -> Duration {
+        self.state
+            .lock()
+            .expect("clock lock should not be poisoned")
+            .1
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state
+            .lock()
+            .expect("clock lock should not be poisoned")
+            .0
+    }
+
+    fn instant(&self) -> Instant {
+        self.base_instant + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.advance(duration);
+        Box::pin(std::future::ready(()))
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::counter;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// Spawn a task calling `collect` every `interval`
+///
+/// The task is supervised: if it dies (because `collect` panicked),
+/// the failure is logged, `foobar_metrics_collector_restarts_total` is
+/// incremented, and the task is restarted after a delay, which grows
+/// exponentially on consecutive failures.
+pub fn spawn_metrics_collector<F>(interval: Duration, collect: F) -> JoinHandle<()>
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let collect = Arc::new(collect);
+    tokio::spawn(async move {
+        let mut restart_delay = MIN_RESTART_DELAY;
+        loop {
+            let started = Instant::now();
+            let task = tokio::spawn({
+                let collect = collect.clone();
+                async move {
+                    loop {
+                        collect();
+                        tokio::time::sleep(interval).await;
+                    }
+                }
+            });
+
+            // the task never completes normally
+            let Err(error) = task.await;
+            error!(%error, "metrics collector task failed, restarting");
+            counter!("foobar_metrics_collector_restarts_total").increment(1);
+
+            // don't penalize failures which happen after long healthy run
+            if started.elapsed() > MAX_RESTART_DELAY {
+                restart_delay = MIN_RESTART_DELAY;
+            }
+            tokio::time::sleep(restart_delay).await;
+            restart_delay = (restart_delay * 2).min(MAX_RESTART_DELAY);
+        }
+    })
+}
@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Cron schedule expressions
+//!
+//! Supports classic five field expressions (`minute hour day-of-month
+//! month day-of-week`) with lists, ranges and steps (for instance,
+//! `*/15 9-17 * * 1-5`), and `@hourly`, `@daily`, `@weekly`, `@monthly`
+//! and `@yearly` shortcuts. As in cron, if both day of month and day of
+//! week are restricted, time matches if either of these matches.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// How far ahead matching time is looked up, enough to cover leap years
+const SEARCH_LIMIT_DAYS: u64 = 366 * 8;
+
+/// Maximal length of DST gap, after which local time exists again
+const MAX_DST_GAP_MINUTES: i64 = 180;
+
+/// Parse single field into bit set of allowed values
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step \"{step}\""))?,
+            ),
+            None => (item, 1),
+        };
+        let parse_value = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(|| format!("invalid value \"{value}\", expected {min}-{max}"))
+        };
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else if let Some((first, last)) = range.split_once('-') {
+            (parse_value(first)?, parse_value(last)?)
+        } else {
+            let value = parse_value(range)?;
+            // `N/step` means from N up to the maximum, as in cron
+            (value, if step > 1 { max } else { value })
+        };
+        if first > last {
+            return Err(format!("invalid range \"{range}\""));
+        }
+        for value in (first..=last).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// Parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronSchedule {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day_of_month = self.days_of_month & (1 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+
+    /// Resolve local time into an instant, taking DST transitions into account
+    ///
+    /// Time repeated when clocks go back matches its first occurrence
+    /// only, and time skipped when clocks go forward matches the first
+    /// instant after the gap, so jobs are neither run twice nor lost.
+    fn resolve<Tz: TimeZone>(tz: &Tz, time: NaiveDateTime) -> Option<DateTime<Tz>> {
+        tz.from_local_datetime(&time).earliest().or_else(|| {
+            (1..=MAX_DST_GAP_MINUTES).find_map(|minutes| {
+                tz.from_local_datetime(&(time + TimeDelta::minutes(minutes)))
+                    .earliest()
+            })
+        })
+    }
+
+    /// First time matching the schedule which is strictly after given one
+    ///
+    /// Matching is done in the time zone of given time. Returns `None`
+    /// if there's no matching time in the foreseeable future.
+    pub fn next_after<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = time.timezone();
+        let start = time.naive_local().with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+
+        for date in start.date().iter_days().take(SEARCH_LIMIT_DAYS as usize) {
+            if !self.matches_date(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|hour| self.hours & (1 << hour) != 0) {
+                for minute in (0..60).filter(|minute| self.minutes & (1 << minute) != 0) {
+                    let candidate = date.and_hms_opt(hour, minute, 0)?;
+                    if candidate < start {
+                        continue;
+                    }
+                    if let Some(result) = Self::resolve(&tz, candidate)
+                        && result > *time
+                    {
+                        return Some(result);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = match s.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            expression => expression,
+        };
+        let invalid = |err: String| format!("invalid cron expression \"{s}\": {err}");
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(invalid(format!("expected 5 fields, got {}", fields.len())));
+        };
+
+        let mut schedule = Self {
+            source: s.trim().to_string(),
+            minutes: parse_field(minutes, 0, 59).map_err(invalid)?,
+            hours: parse_field(hours, 0, 23).map_err(invalid)?,
+            days_of_month: parse_field(days_of_month, 1, 31).map_err(invalid)?,
+            months: parse_field(months, 1, 12).map_err(invalid)?,
+            days_of_week: parse_field(days_of_week, 0, 7).map_err(invalid)?,
+            days_of_month_restricted: days_of_month != "*",
+            days_of_week_restricted: days_of_week != "*",
+        };
+        // both 0 and 7 mean Sunday
+        if schedule.days_of_week & (1 << 7) != 0 {
+            schedule.days_of_week |= 1;
+        }
+
+        if schedule.next_after(&DateTime::UNIX_EPOCH).is_none() {
+            return Err(invalid("never matches".to_string()));
+        }
+
+        Ok(schedule)
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CronSchedule> for String {
+    fn from(schedule: CronSchedule) -> Self {
+        schedule.source
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
@@ -0,0 +1,158 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::Context;
+use metrics::counter;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Executor, PgPool};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::clock::Clock;
+use crate::redact::redact_dsn;
+
+/// Number of prepared statements cached per connection by sqlx by default
+pub const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+#[derive(Debug)]
+pub struct PoolConfig {
+    /// Name reported to PostgreSQL as `application_name`
+    pub application_name: &'static str,
+
+    /// Number of prepared statements cached per connection
+    ///
+    /// When not set, sqlx default ([`DEFAULT_STATEMENT_CACHE_CAPACITY`])
+    /// is used. Larger cache saves
+    /// re-preparing statements for workloads with many distinct queries,
+    /// at the cost of memory on both client and server side, for every
+    /// connection in the pool. Zero disables caching completely.
+    pub statement_cache_capacity: Option<usize>,
+}
+
+pub async fn create_pool(
+    connect_options: PgConnectOptions,
+    config: &PoolConfig,
+) -> Result<PgPool, sqlx::Error> {
+    let connect_options = if let Some(capacity) = config.statement_cache_capacity {
+        connect_options.statement_cache_capacity(capacity)
+    } else {
+        connect_options
+    };
+
+    let application_name = config.application_name;
+
+    PgPoolOptions::new()
+        // ping connections before handing them out, so ones broken by
+        // PostgreSQL restart are replaced instead of failing queries
+        .test_before_acquire(true)
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                conn.execute(format!("SET application_name = '{application_name}'").as_str())
+                    .await?;
+                Ok(())
+            })
+        })
+        .connect_with(connect_options)
+        .await
+}
+
+/// Create connection pool for the given DSN
+///
+/// Error messages refer to the DSN with password redacted.
+pub async fn connect(dsn: &str, config: &PoolConfig) -> anyhow::Result<PgPool> {
+    let connect_options = PgConnectOptions::from_str(dsn)
+        .with_context(|| format!("cannot parse DSN {}", redact_dsn(dsn)))?;
+    create_pool(connect_options, config).await.with_context(|| {
+        format!(
+            "error creating PostgreSQL connection pool for {}",
+            redact_dsn(dsn)
+        )
+    })
+}
+
+/// Delay before the first retry, doubled for each subsequent one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// Whether the error is transient, so the failed operation may succeed if retried
+///
+/// These are serialization failures and deadlocks, as well as lost
+/// connections, e.g. during PostgreSQL failover. Pool acquire timeouts
+/// are not considered transient, as retrying would only add to the load.
+pub fn is_transient_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_) => true,
+        sqlx::Error::Database(err) => err.code().is_some_and(|code| {
+            // serialization_failure, deadlock_detected, admin_shutdown,
+            // or any of connection_exception class
+            matches!(&*code, "40001" | "40P01" | "57P01") || code.starts_with("08")
+        }),
+        _ => false,
+    }
+}
+
+/// Run database operation, retrying it on transient errors
+///
+/// Operation is retried up to `retries` times, with exponential
+/// backoff. Other errors are returned immediately. Operation should
+/// start its own transaction, as failed one cannot be reused.
+pub async fn retry_transient<T, F, Fut>(
+    clock: &dyn Clock,
+    retries: u32,
+    mut operation: F,
+) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Err(err) if attempt < retries && is_transient_error(&err) => {
+                let delay = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt));
+                attempt += 1;
+                warn!(
+                    error = %err,
+                    attempt,
+                    ?delay,
+                    "retrying database operation after transient error"
+                );
+                clock.sleep(delay).await;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Spawn a task checking database availability every `interval`
+///
+/// The first check is done right away. Dead connections are replaced
+/// by the pool on acquire, so the pool recovers from PostgreSQL
+/// restarts by itself; this task makes outages and recoveries
+/// visible. When the database becomes available after failed check,
+/// including one done at startup, that is logged and
+/// `foobar_db_pool_rebuilds_total` is incremented.
+pub fn spawn_pool_health_probe(pool: PgPool, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // unknown until the first check
+        let mut healthy = None;
+        loop {
+            match pool.execute("SELECT 1").await {
+                Ok(_) if healthy == Some(false) => {
+                    info!("database connection recovered");
+                    counter!("foobar_db_pool_rebuilds_total").increment(1);
+                    healthy = Some(true);
+                }
+                Ok(_) => healthy = Some(true),
+                Err(error) if healthy != Some(false) => {
+                    error!(%error, "database health probe failed");
+                    healthy = Some(false);
+                }
+                Err(_) => {}
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
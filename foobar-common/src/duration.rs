@@ -0,0 +1,166 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Human readable durations and sizes in configuration
+//!
+//! Settings which are durations accept values like `30s`, `5m` or
+//! `1h30m`, as well as bare numbers which are treated as seconds.
+//! Settings which are sizes accept values like `64KiB` or `1MiB`,
+//! as well as bare numbers which are treated as bytes.
+
+use std::fmt;
+use std::num::NonZeroU64;
+
+use serde::Deserializer;
+use serde::de::{self, Visitor};
+
+/// Parse duration such as `30s`, `5m` or `1h30m` into whole seconds
+///
+/// Supported units are `s`, `m`, `h` and `d`. Number without unit
+/// is treated as seconds. Zero duration is rejected.
+pub fn parse_seconds(value: &str) -> Result<NonZeroU64, String> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<NonZeroU64>() {
+        return Ok(seconds);
+    }
+
+    let invalid = || format!("invalid duration \"{value}\", expected e.g. \"30s\" or \"5m\"");
+
+    let mut total: u64 = 0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let number: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let multiplier = match &rest[..unit_len] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+        total = number
+            .checked_mul(multiplier)
+            .and_then(|seconds| total.checked_add(seconds))
+            .ok_or_else(|| format!("duration \"{value}\" is too large"))?;
+    }
+
+    NonZeroU64::new(total).ok_or_else(|| format!("duration \"{value}\" must not be zero"))
+}
+
+struct SecondsVisitor;
+
+impl Visitor<'_> for SecondsVisitor {
+    type Value = Option<NonZeroU64>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("positive number of seconds or duration string such as \"5m\"")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        u64::try_from(value)
+            .ok()
+            .and_then(NonZeroU64::new)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        NonZeroU64::new(value)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        parse_seconds(value).map(Some).map_err(E::custom)
+    }
+}
+
+/// Deserialize optional duration setting into whole seconds
+///
+/// For use with `#[serde(deserialize_with = "...")]` on
+/// `Option<NonZeroU64>` fields; accepts both numbers and strings
+/// understood by [`parse_seconds`].
+pub fn deserialize_seconds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NonZeroU64>, D::Error> {
+    deserializer.deserialize_any(SecondsVisitor)
+}
+
+/// Parse size such as `512B`, `64KiB` or `1MiB` into bytes
+///
+/// Supported units are `B`, `KiB`, `MiB` and `GiB`. Number without
+/// unit is treated as bytes. Zero size is rejected.
+pub fn parse_size(value: &str) -> Result<usize, String> {
+    let value = value.trim();
+    let invalid = || format!("invalid size \"{value}\", expected e.g. \"64KiB\" or \"1MiB\"");
+
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    if digits == 0 {
+        return Err(invalid());
+    }
+    let number: usize = value[..digits]
+        .parse()
+        .map_err(|_| format!("size \"{value}\" is too large"))?;
+    let multiplier = match &value[digits..] {
+        "" | "B" => 1,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        _ => return Err(invalid()),
+    };
+    match number.checked_mul(multiplier) {
+        Some(0) => Err(format!("size \"{value}\" must not be zero")),
+        Some(bytes) => Ok(bytes),
+        None => Err(format!("size \"{value}\" is too large")),
+    }
+}
+
+struct SizeVisitor;
+
+impl Visitor<'_> for SizeVisitor {
+    type Value = Option<usize>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("positive number of bytes or size string such as \"1MiB\"")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        usize::try_from(value)
+            .ok()
+            .filter(|&bytes| bytes != 0)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        usize::try_from(value)
+            .ok()
+            .filter(|&bytes| bytes != 0)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        parse_size(value).map(Some).map_err(E::custom)
+    }
+}
+
+/// Deserialize optional size setting into bytes
+///
+/// For use with `#[serde(deserialize_with = "...")]` on
+/// `Option<usize>` fields; accepts both numbers and strings
+/// understood by [`parse_size`].
+pub fn deserialize_size<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    deserializer.deserialize_any(SizeVisitor)
+}
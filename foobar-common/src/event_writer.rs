@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Writer for `tracing_subscriber` fmt layer which passes each
+//! formatted event, along with its level, to an arbitrary sink
+
+use std::io::Write;
+use std::sync::Arc;
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Destination for formatted events
+pub trait EventSink: Send + Sync + 'static {
+    /// Handle single event, with trailing newline stripped
+    ///
+    /// Called from the thread which produced the event, so must not
+    /// block. Delivery is best effort, as there's no place to report
+    /// errors to anyway.
+    fn send(&self, level: Level, message: &str);
+}
+
+/// Writer which passes each formatted event to the sink
+pub struct EventWriter<T>(Arc<T>);
+
+impl<T: EventSink> EventWriter<T> {
+    pub fn new(sink: Arc<T>) -> Self {
+        Self(sink)
+    }
+
+    fn message(&self, level: &Level) -> EventMessage<T> {
+        EventMessage {
+            sink: self.0.clone(),
+            level: *level,
+            buffer: vec![],
+        }
+    }
+}
+
+impl<T> Clone for EventWriter<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Buffer for a single event, which is passed to the sink when dropped
+pub struct EventMessage<T: EventSink> {
+    sink: Arc<T>,
+    level: Level,
+    buffer: Vec<u8>,
+}
+
+impl<T: EventSink> Write for EventMessage<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T: EventSink> Drop for EventMessage<T> {
+    fn drop(&mut self) {
+        let message = String::from_utf8_lossy(&self.buffer);
+        let message = message.trim_end();
+        if !message.is_empty() {
+            self.sink.send(self.level, message);
+        }
+    }
+}
+
+impl<'a, T: EventSink> MakeWriter<'a> for EventWriter<T> {
+    type Writer = EventMessage<T>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.message(&Level::INFO)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        self.message(meta.level())
+    }
+}
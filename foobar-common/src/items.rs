@@ -0,0 +1,15 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// Maximal length of item text, in characters
+///
+/// Also enforced by `CHECK` constraint on `items.text` in the
+/// database, which must be kept in sync with this value.
+pub const MAX_ITEM_TEXT_LENGTH: usize = 1024;
+
+/// Check whether text fits into item text length limit
+///
+/// Length is counted in characters, same as PostgreSQL `char_length()`.
+pub fn is_valid_item_text(text: &str) -> bool {
+    text.chars().count() <= MAX_ITEM_TEXT_LENGTH
+}
@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Queue of background jobs
+//!
+//! Jobs are claimed with `FOR UPDATE SKIP LOCKED`, so any number of
+//! workers may pull from the queue concurrently: a claimed job stays
+//! locked until the claiming transaction ends, and is skipped by
+//! other claimers meanwhile. Job is expected to be completed in the
+//! same transaction; if the transaction is rolled back (e.g. worker
+//! fails), the job becomes available again.
+
+use indoc::indoc;
+use sqlx::{FromRow, PgConnection, PgExecutor};
+
+#[derive(FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub id: i64,
+    pub kind: String,
+    pub payload: String,
+}
+
+/// Add job to the queue, returning its id
+pub async fn enqueue_job(
+    executor: impl PgExecutor<'_>,
+    kind: &str,
+    payload: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("INSERT INTO jobs(kind, payload) VALUES($1, $2) RETURNING id")
+        .bind(kind)
+        .bind(payload)
+        .fetch_one(executor)
+        .await
+}
+
+/// Claim up to `limit` oldest pending jobs not claimed by others
+///
+/// Must be called within a transaction, which holds the claim.
+pub async fn claim_next(conn: &mut PgConnection, limit: i64) -> Result<Vec<Job>, sqlx::Error> {
+    sqlx::query_as(indoc! {"
+        SELECT id, kind, payload
+        FROM jobs
+        WHERE completed_at IS NULL
+        ORDER BY id
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+    "})
+    .bind(limit)
+    .fetch_all(conn)
+    .await
+}
+
+/// Mark claimed job as completed, so it's never claimed again
+pub async fn complete_job(conn: &mut PgConnection, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE jobs SET completed_at = now() WHERE id = $1")
+        .bind(id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
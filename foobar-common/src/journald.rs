@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Logging to systemd journal
+//!
+//! Implements native journal protocol: each event is sent as a single
+//! datagram of `KEY=value` entries, so event fields, as well as fields
+//! of all enclosing spans, are preserved as separate journal fields
+//! (for instance, `request_id` becomes `REQUEST_ID`).
+
+use std::fmt::Debug;
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::syslog::syslog_severity;
+
+const JOURNALD_SOCKET_PATH: &str = "/run/systemd/journal/socket";
+
+/// Convert `tracing` field name into valid journal field name
+///
+/// Journal field names may only contain uppercase letters, digits
+/// and underscores, and may not start with an underscore.
+fn field_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.trim_start_matches('_').to_string()
+}
+
+fn put_field(buffer: &mut Vec<u8>, name: &str, value: &[u8]) {
+    buffer.extend_from_slice(name.as_bytes());
+    if value.contains(&b'\n') {
+        // multiline values require binary serialization
+        buffer.push(b'\n');
+        buffer.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        buffer.push(b'=');
+    }
+    buffer.extend_from_slice(value);
+    buffer.push(b'\n');
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<u8>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        put_field(self.0, &field_name(field.name()), value.as_bytes());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        put_field(
+            self.0,
+            &field_name(field.name()),
+            format!("{value:?}").as_bytes(),
+        );
+    }
+}
+
+/// Serialized fields of a span, stored in its extensions
+struct SpanFields(Vec<u8>);
+
+/// `tracing` layer which sends events to systemd journal
+pub struct JournaldLayer {
+    socket: UnixDatagram,
+    ident: String,
+}
+
+impl JournaldLayer {
+    pub fn new(ident: &str) -> std::io::Result<Self> {
+        Self::with_socket_path(ident, JOURNALD_SOCKET_PATH)
+    }
+
+    pub fn with_socket_path(ident: &str, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(path)?;
+        Ok(Self {
+            socket,
+            ident: ident.to_string(),
+        })
+    }
+}
+
+impl<S> Layer<S> for JournaldLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut buffer = vec![];
+        attrs.record(&mut FieldVisitor(&mut buffer));
+        span.extensions_mut().insert(SpanFields(buffer));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+            values.record(&mut FieldVisitor(&mut fields.0));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let priority = syslog_severity(meta.level()).to_string();
+
+        let mut buffer = vec![];
+        put_field(&mut buffer, "PRIORITY", priority.as_bytes());
+        put_field(&mut buffer, "SYSLOG_IDENTIFIER", self.ident.as_bytes());
+        put_field(&mut buffer, "TARGET", meta.target().as_bytes());
+        if let Some(file) = meta.file() {
+            put_field(&mut buffer, "CODE_FILE", file.as_bytes());
+        }
+        if let Some(line) = meta.line() {
+            put_field(&mut buffer, "CODE_LINE", line.to_string().as_bytes());
+        }
+
+        for span in ctx.event_scope(event).into_iter().flatten() {
+            if let Some(fields) = span.extensions().get::<SpanFields>() {
+                buffer.extend_from_slice(&fields.0);
+            }
+        }
+
+        // `message` field naturally becomes `MESSAGE`
+        event.record(&mut FieldVisitor(&mut buffer));
+
+        let _ = self.socket.send(&buffer);
+    }
+}
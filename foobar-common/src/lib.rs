@@ -1,6 +1,48 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+pub mod audit;
+pub mod auth;
+pub mod clock;
+pub mod collector;
+pub mod cron;
+pub mod database;
+pub mod duration;
+pub mod event_writer;
+pub mod items;
+pub mod jobs;
+pub mod journald;
+pub mod log_throttle;
+pub mod log_timer;
+pub mod loki;
 pub mod migrations;
+pub mod recorder;
+pub mod redact;
+pub mod settings;
+pub mod statsd;
+pub mod syslog;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod udp;
 
+pub use audit::*;
+pub use auth::*;
+pub use clock::*;
+pub use collector::*;
+pub use cron::*;
+pub use database::*;
+pub use duration::*;
+pub use event_writer::*;
+pub use items::*;
+pub use jobs::*;
+pub use journald::*;
+pub use log_throttle::*;
+pub use log_timer::*;
+pub use loki::*;
 pub use migrations::*;
+pub use recorder::*;
+pub use redact::*;
+pub use settings::*;
+pub use statsd::*;
+pub use syslog::*;
+pub use udp::*;
@@ -0,0 +1,58 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::SharedClock;
+
+/// Limits logging of repeated events, such as persistent errors
+///
+/// First event is logged, and following ones are suppressed until
+/// `period` passes, after which the next event is logged again
+/// along with the number of events suppressed in between.
+pub struct LogThrottle {
+    clock: SharedClock,
+    period: Duration,
+    last_logged: Option<Instant>,
+    suppressed: u64,
+}
+
+impl LogThrottle {
+    pub fn new(period: Duration, clock: SharedClock) -> Self {
+        Self {
+            clock,
+            period,
+            last_logged: None,
+            suppressed: 0,
+        }
+    }
+
+    /// Register an event
+    ///
+    /// Returns `None` if the event should not be logged, otherwise
+    /// the number of events suppressed since the last logged one.
+    pub fn event(&mut self) -> Option<u64> {
+        let now = self.clock.instant();
+        match self.last_logged {
+            Some(last_logged) if now.duration_since(last_logged) < self.period => {
+                self.suppressed += 1;
+                None
+            }
+            _ => {
+                self.last_logged = Some(now);
+                Some(std::mem::take(&mut self.suppressed))
+            }
+        }
+    }
+
+    /// Reset throttling once the events have stopped
+    ///
+    /// Returns the number of events suppressed since the last logged
+    /// one, so they can be summarized.
+    pub fn reset(&mut self) -> u64 {
+        self.last_logged = None;
+        std::mem::take(&mut self.suppressed)
+    }
+}
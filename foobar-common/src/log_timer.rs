@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::format::{Item, StrftimeItems};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{ChronoLocal, ChronoUtc, FormatTime};
+
+/// Default format of log timestamps
+pub const DEFAULT_LOG_TIMESTAMP_FORMAT: &str = "%F %T%.6f";
+
+/// Timestamp formatter for log output, in either local time or UTC
+#[derive(Clone)]
+pub enum LogTimer {
+    Local(ChronoLocal),
+    Utc(ChronoUtc),
+}
+
+impl LogTimer {
+    /// Create timer with given `strftime`-like format
+    ///
+    /// Fails if the format is not valid.
+    pub fn new(format: &str, utc: bool) -> anyhow::Result<Self> {
+        if StrftimeItems::new(format).any(|item| item == Item::Error) {
+            anyhow::bail!("invalid timestamp format \"{format}\"");
+        }
+        Ok(if utc {
+            Self::Utc(ChronoUtc::new(format.to_string()))
+        } else {
+            Self::Local(ChronoLocal::new(format.to_string()))
+        })
+    }
+}
+
+impl FormatTime for LogTimer {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local(timer) => timer.format_time(w),
+            Self::Utc(timer) => timer.format_time(w),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Logging to Grafana Loki
+//!
+//! Events are formatted by the regular `tracing_subscriber` fmt layer,
+//! same as for syslog, and put into a bounded in-memory queue, from
+//! which a background task pushes them to Loki in batches. Logging
+//! never waits for Loki: when the queue is full (e.g. during a Loki
+//! outage) the oldest entries are dropped and counted in the
+//! `foobar_loki_dropped_logs_total` metric, while failed pushes are
+//! retried with exponential backoff.
+//!
+//! `tracing-loki` crate is not used, as it keeps an unbounded queue
+//! during outages and drops events without a way to account for them.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use metrics::counter;
+use serde_json::json;
+use tokio::sync::Notify;
+use tracing::instrument::WithSubscriber;
+use tracing::subscriber::NoSubscriber;
+use tracing::{Level, Subscriber, info, warn};
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+use url::Url;
+
+use crate::event_writer::{EventSink, EventWriter};
+
+/// Default number of events kept while Loki is unreachable
+pub const DEFAULT_LOKI_QUEUE_SIZE: usize = 10000;
+
+const MAX_BATCH_SIZE: usize = 1000;
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+struct Entry {
+    timestamp: u128,
+    level: Level,
+    line: String,
+}
+
+struct Queue {
+    entries: Mutex<VecDeque<Entry>>,
+    capacity: usize,
+    ready: Notify,
+}
+
+impl Queue {
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Entry>> {
+        self.entries
+            .lock()
+            .expect("loki queue lock should not be poisoned")
+    }
+
+    fn drop_excess(&self, entries: &mut VecDeque<Entry>) {
+        let excess = entries.len().saturating_sub(self.capacity);
+        if excess > 0 {
+            entries.drain(..excess);
+            counter!("foobar_loki_dropped_logs_total").increment(excess as u64);
+        }
+    }
+
+    fn push(&self, entry: Entry) {
+        {
+            let mut entries = self.lock();
+            entries.push_back(entry);
+            self.drop_excess(&mut entries);
+        }
+        self.ready.notify_one();
+    }
+
+    fn take(&self) -> Vec<Entry> {
+        let mut entries = self.lock();
+        let count = entries.len().min(MAX_BATCH_SIZE);
+        entries.drain(..count).collect()
+    }
+
+    /// Put back batch which failed to be pushed
+    ///
+    /// The batch is older than anything queued since it was taken,
+    /// so it goes to the front and is the first to be dropped.
+    fn restore(&self, batch: Vec<Entry>) {
+        let mut entries = self.lock();
+        let mut restored: VecDeque<Entry> = batch.into();
+        restored.append(&mut entries);
+        self.drop_excess(&mut restored);
+        *entries = restored;
+    }
+}
+
+impl EventSink for Queue {
+    fn send(&self, level: Level, message: &str) {
+        self.push(Entry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+            level,
+            line: message.to_string(),
+        });
+    }
+}
+
+/// Background task which pushes queued events to Loki
+///
+/// Must be spawned for events to be delivered.
+pub struct LokiTask {
+    queue: Arc<Queue>,
+    client: reqwest::Client,
+    push_url: Url,
+    service: String,
+}
+
+impl LokiTask {
+    pub async fn run(self) {
+        let mut retry_delay = MIN_RETRY_DELAY;
+        let mut reachable = true;
+        loop {
+            let batch = self.queue.take();
+            if batch.is_empty() {
+                self.queue.ready.notified().await;
+                continue;
+            }
+            // events produced while pushing (e.g. by the HTTP client)
+            // must not get into the queue, or they would never drain
+            match self.push(&batch).with_subscriber(NoSubscriber::new()).await {
+                Ok(()) => {
+                    if !reachable {
+                        info!("loki is reachable again, resuming log delivery");
+                        reachable = true;
+                    }
+                    retry_delay = MIN_RETRY_DELAY;
+                }
+                Err(err) => {
+                    self.queue.restore(batch);
+                    if reachable {
+                        warn!("loki is unreachable, logs are queued until it recovers: {err:#}");
+                        reachable = false;
+                    }
+                    tokio::time::sleep(retry_delay).await;
+                    retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                }
+            }
+        }
+    }
+
+    async fn push(&self, batch: &[Entry]) -> anyhow::Result<()> {
+        let mut streams: Vec<(Level, Vec<[String; 2]>)> = vec![];
+        for entry in batch {
+            let value = [entry.timestamp.to_string(), entry.line.clone()];
+            match streams.iter_mut().find(|(level, _)| *level == entry.level) {
+                Some((_, values)) => values.push(value),
+                None => streams.push((entry.level, vec![value])),
+            }
+        }
+        let body = json!({
+            "streams": streams
+                .into_iter()
+                .map(|(level, values)| json!({
+                    "stream": {
+                        "service": self.service,
+                        "level": level.as_str().to_lowercase(),
+                    },
+                    "values": values,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        self.client
+            .post(self.push_url.clone())
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Create `tracing` layer which sends events to Loki
+///
+/// Returned task must be spawned to deliver the events. Events are
+/// labeled with `service` and `level`, and at most `queue_size` of
+/// them are kept while Loki cannot be reached.
+pub fn loki_layer<S>(
+    service: &str,
+    url: &Url,
+    queue_size: usize,
+) -> anyhow::Result<(Box<dyn Layer<S> + Send + Sync>, LokiTask)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let queue = Arc::new(Queue {
+        entries: Mutex::new(VecDeque::new()),
+        capacity: queue_size,
+        ready: Notify::new(),
+    });
+    let task = LokiTask {
+        queue: queue.clone(),
+        client: reqwest::Client::builder()
+            .timeout(PUSH_TIMEOUT)
+            .build()
+            .context("cannot create http client")?,
+        push_url: url.join("loki/api/v1/push").context("invalid loki url")?,
+        service: service.to_string(),
+    };
+    let layer = tracing_subscriber::fmt::Layer::new()
+        .with_ansi(false)
+        .without_time()
+        .with_level(false)
+        .with_writer(EventWriter::new(queue))
+        .boxed();
+    Ok((layer, task))
+}
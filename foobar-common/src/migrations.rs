@@ -1,4 +1,229 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::time::Duration;
+
+use anyhow::{Context, anyhow, bail};
+use indoc::indoc;
+use sqlx::migrate::{MigrateError, Migration};
+use sqlx::{FromRow, PgConnection, PgPool};
+
 pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("../migrations");
+
+/// Name of PostgreSQL schema holding application data
+pub const SCHEMA: &str = "foobar";
+
+/// Default time to wait for locks when running migrations
+pub const DEFAULT_MIGRATION_LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// SQLSTATE of `lock_not_available` error
+const LOCK_NOT_AVAILABLE: &str = "55P03";
+
+/// Create application schema and apply pending migrations
+///
+/// Migrations are serialized with an advisory lock, and waiting for
+/// it, as well as for any lock needed by the migrations, is limited
+/// by `lock_timeout`, so an instance stuck holding the lock makes
+/// startup fail instead of hanging indefinitely.
+pub async fn run_migrations(pool: &PgPool, lock_timeout: Duration) -> anyhow::Result<()> {
+    sqlx::query(&format!("CREATE SCHEMA IF NOT EXISTS {SCHEMA}"))
+        .execute(pool)
+        .await
+        .context("failed to create schema")?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("failed to acquire connection for migrations")?;
+    // timeout is set for the session, so it must not leak into the pool
+    conn.close_on_drop();
+    sqlx::query(&format!("SET lock_timeout = {}", lock_timeout.as_millis()))
+        .execute(&mut *conn)
+        .await
+        .context("failed to set lock timeout")?;
+
+    MIGRATOR.run(&mut *conn).await.map_err(|err| match &err {
+        // lock wait errors in the migrations themselves are reported
+        // as ExecuteMigration, so this can only be the migrator lock
+        MigrateError::Execute(sqlx::Error::Database(db_err))
+            if db_err.code().as_deref() == Some(LOCK_NOT_AVAILABLE) =>
+        {
+            anyhow!(
+                "failed to run migrations: another instance is holding the migration lock (waited for {lock_timeout:?})"
+            )
+        }
+        _ => anyhow::Error::new(err).context("failed to run migrations"),
+    })?;
+
+    Ok(())
+}
+
+/// Check that all known migrations are applied, without modifying the database
+///
+/// For the cases where migrations are managed externally, or the
+/// database is read-only replica. Modified migrations and migrations
+/// applied by newer code are tolerated, since the tables they
+/// provide are in place.
+pub async fn verify_migrations(pool: &PgPool) -> anyhow::Result<()> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("failed to acquire connection for checking migrations")?;
+    let status = get_migration_status(&mut conn)
+        .await
+        .context("failed to get migration status")?;
+
+    let missing: Vec<String> = status
+        .iter()
+        .filter(|status| {
+            matches!(
+                status.state,
+                MigrationState::Pending | MigrationState::Failed
+            )
+        })
+        .map(|status| format!("{} ({})", status.version, status.state.as_str()))
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "database schema is not up to date, migrations not applied: {}",
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Migration as recorded in the database
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub checksum: Vec<u8>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Applied and matches the known migration
+    Applied,
+    /// Known, but not applied yet
+    Pending,
+    /// Applied, but checksum differs from the known migration
+    ChecksumMismatch,
+    /// Application was attempted, but failed
+    Failed,
+    /// Applied, but not known (e.g. applied by newer version of the code)
+    Unknown,
+}
+
+impl MigrationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Pending => "pending",
+            Self::ChecksumMismatch => "checksum_mismatch",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    /// Checksum of the known migration, or of the applied one if it's unknown
+    pub checksum: Vec<u8>,
+    pub state: MigrationState,
+}
+
+impl MigrationStatus {
+    /// Whether the migration needs attention before the code can be run
+    pub fn is_problem(&self) -> bool {
+        self.state != MigrationState::Applied
+    }
+}
+
+/// Compare known migrations against the applied ones
+///
+/// Result is ordered by version. Down migrations are ignored.
+pub fn compare_migrations<'a>(
+    known: impl IntoIterator<Item = &'a Migration>,
+    applied: &[AppliedMigration],
+) -> Vec<MigrationStatus> {
+    let mut res: Vec<MigrationStatus> = known
+        .into_iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| {
+            let state = match applied.iter().find(|a| a.version == migration.version) {
+                None => MigrationState::Pending,
+                Some(applied) if !applied.success => MigrationState::Failed,
+                Some(applied) if *applied.checksum != *migration.checksum => {
+                    MigrationState::ChecksumMismatch
+                }
+                Some(_) => MigrationState::Applied,
+            };
+            MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                checksum: migration.checksum.to_vec(),
+                state,
+            }
+        })
+        .collect();
+
+    let unknown: Vec<MigrationStatus> = applied
+        .iter()
+        .filter(|applied| !res.iter().any(|known| known.version == applied.version))
+        .map(|applied| MigrationStatus {
+            version: applied.version,
+            description: applied.description.clone(),
+            checksum: applied.checksum.clone(),
+            state: if applied.success {
+                MigrationState::Unknown
+            } else {
+                MigrationState::Failed
+            },
+        })
+        .collect();
+
+    res.extend(unknown);
+    res.sort_by_key(|status| status.version);
+    res
+}
+
+/// Fetch migrations recorded in the database
+///
+/// Does not modify the database; if migrations table does not
+/// exist yet, no migrations are returned.
+pub async fn get_applied_migrations(
+    conn: &mut PgConnection,
+) -> Result<Vec<AppliedMigration>, sqlx::Error> {
+    let (table_exists,): (bool,) =
+        sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    if !table_exists {
+        return Ok(vec![]);
+    }
+
+    sqlx::query_as(indoc! {r#"
+        SELECT
+            version,
+            description,
+            checksum,
+            success
+        FROM _sqlx_migrations
+        ORDER BY version
+    "#})
+    .fetch_all(&mut *conn)
+    .await
+}
+
+/// Get status of all known and applied migrations
+pub async fn get_migration_status(
+    conn: &mut PgConnection,
+) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let applied = get_applied_migrations(conn).await?;
+    Ok(compare_migrations(MIGRATOR.iter(), &applied))
+}
@@ -0,0 +1,23 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use anyhow::anyhow;
+use metrics::Recorder;
+use metrics_util::layers::{Layer as _, PrefixLayer};
+
+/// Install global metrics recorder
+///
+/// If `prefix` is given, it's prepended to names of all metrics,
+/// separated with a dot (which Prometheus exporter converts into
+/// an underscore), so metrics of multiple services can be told apart.
+pub fn install_recorder<R>(recorder: R, prefix: Option<&str>) -> anyhow::Result<()>
+where
+    R: Recorder + Send + Sync + 'static,
+{
+    let recorder: Box<dyn Recorder + Send + Sync> = match prefix {
+        Some(prefix) => Box::new(PrefixLayer::new(prefix).layer(recorder)),
+        None => Box::new(recorder),
+    };
+    metrics::set_global_recorder(recorder)
+        .map_err(|_| anyhow!("metrics recorder is already installed"))
+}
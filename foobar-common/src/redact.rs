@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use url::Url;
+
+const MASK: &str = "***";
+
+/// Mask password in an URL, if any
+pub fn redact_url(url: &Url) -> Url {
+    let mut url = url.clone();
+    if url.password().is_some() {
+        url.set_password(Some(MASK))
+            .expect("URL with password should be able to hold a password");
+    }
+    url
+}
+
+/// Mask password in a PostgreSQL DSN
+///
+/// Since the DSN may be malformed in a way which prevents locating
+/// the password, unparsable DSN is masked completely.
+pub fn redact_dsn(dsn: &str) -> String {
+    match Url::parse(dsn) {
+        Ok(url) => redact_url(&url).to_string(),
+        Err(_) => MASK.to_string(),
+    }
+}
+
+/// Describe database a PostgreSQL DSN refers to, as `host:port/dbname`
+///
+/// Unlike redacted DSN, this contains no credentials at all, so it's
+/// suitable for logs and diagnostic output.
+pub fn describe_dsn(dsn: &str) -> String {
+    let Ok(url) = Url::parse(dsn) else {
+        return MASK.to_string();
+    };
+    let mut res = url.host_str().unwrap_or("localhost").to_string();
+    if let Some(port) = url.port() {
+        res += &format!(":{port}");
+    }
+    res += url.path();
+    res
+}
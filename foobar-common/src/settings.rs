@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Persistent runtime settings
+//!
+//! Settings are stored in the database as text, converted with
+//! `FromStr` and `Display`, so they survive restarts and are shared
+//! by all application instances using the same database. Settings
+//! read frequently may be served through [`SettingsCache`].
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use indoc::indoc;
+use sqlx::PgExecutor;
+use tokio::time::Instant;
+
+use crate::clock::Clock;
+
+/// Default time during which cached setting values are used
+pub const DEFAULT_SETTINGS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+async fn fetch_setting(executor: impl PgExecutor<'_>, key: &str) -> anyhow::Result<Option<String>> {
+    sqlx::query_scalar("SELECT value FROM settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(executor)
+        .await
+        .with_context(|| format!("cannot read setting \"{key}\""))
+}
+
+fn parse_setting<T>(key: &str, value: Option<String>) -> anyhow::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|err| anyhow::anyhow!("invalid value of setting \"{key}\": {err}"))
+        })
+        .transpose()
+}
+
+/// Read setting, returning `None` if it was never set
+pub async fn get_setting<T>(executor: impl PgExecutor<'_>, key: &str) -> anyhow::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_setting(key, fetch_setting(executor, key).await?)
+}
+
+/// Write setting, replacing its previous value
+pub async fn set_setting<T: Display>(
+    executor: impl PgExecutor<'_>,
+    key: &str,
+    value: &T,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(indoc! {"
+        INSERT INTO settings(key, value)
+        VALUES($1, $2)
+        ON CONFLICT(key) DO UPDATE
+        SET value = excluded.value, updated_at = now()
+    "})
+    .bind(key)
+    .bind(value.to_string())
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// In-process cache of setting values
+///
+/// Each setting is read from the database at most once per `ttl`,
+/// so settings consulted on every request (such as feature flags)
+/// do not add queries, while changes made by other instances become
+/// visible within `ttl`. Changes made through the cache itself are
+/// visible immediately.
+pub struct SettingsCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Option<String>)>>,
+}
+
+impl SettingsCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Default::default(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Option<String>)>> {
+        self.entries
+            .lock()
+            .expect("settings cache lock should not be poisoned")
+    }
+
+    /// Read setting, from the cache if it's fresh enough
+    pub async fn get<T>(
+        &self,
+        clock: &dyn Clock,
+        executor: impl PgExecutor<'_>,
+        key: &str,
+    ) -> anyhow::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let now = clock.instant();
+        let cached = self
+            .lock()
+            .get(key)
+            .filter(|(fetched, _)| now.duration_since(*fetched) < self.ttl)
+            .map(|(_, value)| value.clone());
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = fetch_setting(executor, key).await?;
+                self.lock().insert(key.to_string(), (now, value.clone()));
+                value
+            }
+        };
+        parse_setting(key, value)
+    }
+
+    /// Write setting, updating the cached value as well
+    pub async fn set<T: Display>(
+        &self,
+        clock: &dyn Clock,
+        executor: impl PgExecutor<'_>,
+        key: &str,
+        value: &T,
+    ) -> Result<(), sqlx::Error> {
+        set_setting(executor, key, value).await?;
+        self.lock()
+            .insert(key.to_string(), (clock.instant(), Some(value.to_string())));
+        Ok(())
+    }
+}
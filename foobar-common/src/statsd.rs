@@ -0,0 +1,153 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Metrics exporter for StatsD
+//!
+//! Every metric update is sent as a separate UDP datagram in
+//! DogStatsD format, labels being passed as tags (`name:1|c|#k:v`).
+//! Plain StatsD servers which do not support tags still understand
+//! metrics without labels.
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+
+use crate::udp::connect_udp;
+
+struct StatsdMetric {
+    socket: Arc<UdpSocket>,
+    name: String,
+    tags: String,
+    /// Last absolute value for counters, or current value (as f64 bits) for gauges
+    value: AtomicU64,
+}
+
+impl StatsdMetric {
+    fn send(&self, value: impl std::fmt::Display, kind: &str) {
+        let line = format!("{}:{}|{}{}", self.name, value, kind, self.tags);
+        // delivery is best effort, same as with UDP in general
+        let _ = self.socket.send(line.as_bytes());
+    }
+
+    fn update_gauge(&self, update: impl Fn(f64) -> f64) {
+        let mut current = self.value.load(Ordering::Relaxed);
+        loop {
+            let new = update(f64::from_bits(current));
+            match self.value.compare_exchange_weak(
+                current,
+                new.to_bits(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    self.send(new, "g");
+                    return;
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl CounterFn for StatsdMetric {
+    fn increment(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+        self.send(value, "c");
+    }
+
+    fn absolute(&self, value: u64) {
+        // StatsD counters are deltas, so difference with previous value is sent
+        let previous = self.value.fetch_max(value, Ordering::Relaxed);
+        if value > previous {
+            self.send(value - previous, "c");
+        }
+    }
+}
+
+impl GaugeFn for StatsdMetric {
+    fn increment(&self, value: f64) {
+        self.update_gauge(|current| current + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update_gauge(|current| current - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update_gauge(|_| value);
+    }
+}
+
+impl HistogramFn for StatsdMetric {
+    fn record(&self, value: f64) {
+        self.send(value, "h");
+    }
+}
+
+/// Metrics recorder which sends metrics to StatsD server
+pub struct StatsdRecorder {
+    socket: Arc<UdpSocket>,
+    metrics: Mutex<HashMap<Key, Arc<StatsdMetric>>>,
+}
+
+impl StatsdRecorder {
+    pub fn new(addr: SocketAddr) -> std::io::Result<Self> {
+        let socket = connect_udp(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket: Arc::new(socket),
+            metrics: Default::default(),
+        })
+    }
+
+    /// Get or create state of metric, which is shared by all its handles
+    fn metric(&self, key: &Key) -> Arc<StatsdMetric> {
+        self.metrics
+            .lock()
+            .expect("metrics lock should not be poisoned")
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let tags: Vec<String> = key
+                    .labels()
+                    .map(|label| format!("{}:{}", label.key(), label.value()))
+                    .collect();
+                Arc::new(StatsdMetric {
+                    socket: self.socket.clone(),
+                    name: key.name().to_string(),
+                    tags: if tags.is_empty() {
+                        String::new()
+                    } else {
+                        format!("|#{}", tags.join(","))
+                    },
+                    value: AtomicU64::new(0),
+                })
+            })
+            .clone()
+    }
+}
+
+impl Recorder for StatsdRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.metric(key))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(self.metric(key))
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.metric(key))
+    }
+}
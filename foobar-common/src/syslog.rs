@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Logging to syslog
+//!
+//! Events are formatted by the regular `tracing_subscriber` fmt layer
+//! (so fields are rendered as `key=value` pairs in the message), and
+//! each event is sent as a separate datagram, with syslog severity
+//! derived from event level. Local syslog is fed through `/dev/log`
+//! using the traditional RFC 3164 format, remote servers receive RFC
+//! 5424 messages over UDP.
+
+use std::net::{SocketAddr, UdpSocket};
+use std::os::unix::net::UnixDatagram;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::event_writer::{EventSink, EventWriter};
+use crate::udp::connect_udp;
+
+const LOCAL_SYSLOG_PATH: &str = "/dev/log";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFacility {
+    User,
+    #[default]
+    Daemon,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl SyslogFacility {
+    fn code(self) -> u8 {
+        match self {
+            Self::User => 1,
+            Self::Daemon => 3,
+            Self::Local0 => 16,
+            Self::Local1 => 17,
+            Self::Local2 => 18,
+            Self::Local3 => 19,
+            Self::Local4 => 20,
+            Self::Local5 => 21,
+            Self::Local6 => 22,
+            Self::Local7 => 23,
+        }
+    }
+}
+
+impl FromStr for SyslogFacility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(s))
+            .map_err(|_| format!("unknown syslog facility \"{s}\""))
+    }
+}
+
+/// Syslog severity corresponding to event level
+///
+/// Also used as journal `PRIORITY`, which has the same meaning.
+pub fn syslog_severity(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+enum Socket {
+    Local(UnixDatagram),
+    Remote { socket: UdpSocket, hostname: String },
+}
+
+struct Syslog {
+    socket: Socket,
+    facility: SyslogFacility,
+    ident: String,
+    pid: u32,
+}
+
+impl EventSink for Syslog {
+    fn send(&self, level: Level, message: &str) {
+        let priority = self.facility.code() * 8 + syslog_severity(&level);
+        let _ = match &self.socket {
+            Socket::Local(socket) => socket
+                .send(format!("<{priority}>{}[{}]: {message}", self.ident, self.pid).as_bytes()),
+            Socket::Remote { socket, hostname } => socket.send(
+                format!(
+                    "<{priority}>1 {} {hostname} {} {} - - {message}",
+                    Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+                    self.ident,
+                    self.pid,
+                )
+                .as_bytes(),
+            ),
+        };
+    }
+}
+
+impl Syslog {
+    /// Connect to local syslog, or remote one if address is given
+    fn new(
+        ident: &str,
+        facility: SyslogFacility,
+        addr: Option<SocketAddr>,
+    ) -> std::io::Result<Self> {
+        let socket = if let Some(addr) = addr {
+            let socket = connect_udp(addr)?;
+            let hostname = std::fs::read_to_string("/proc/sys/kernel/hostname")
+                .map(|hostname| hostname.trim().to_string())
+                .unwrap_or_else(|_| "-".to_string());
+            Socket::Remote { socket, hostname }
+        } else {
+            let socket = UnixDatagram::unbound()?;
+            socket.connect(LOCAL_SYSLOG_PATH)?;
+            Socket::Local(socket)
+        };
+        Ok(Self {
+            socket,
+            facility,
+            ident: ident.to_string(),
+            pid: std::process::id(),
+        })
+    }
+}
+
+/// Create `tracing` layer which sends events to syslog
+///
+/// Timestamp and level are not included into the message text,
+/// as syslog conveys these by itself.
+pub fn syslog_layer<S>(
+    ident: &str,
+    facility: SyslogFacility,
+    addr: Option<SocketAddr>,
+) -> std::io::Result<Box<dyn Layer<S> + Send + Sync>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    Ok(tracing_subscriber::fmt::Layer::new()
+        .with_ansi(false)
+        .without_time()
+        .with_level(false)
+        .with_writer(EventWriter::new(Arc::new(Syslog::new(
+            ident, facility, addr,
+        )?)))
+        .boxed())
+}
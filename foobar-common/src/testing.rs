@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Helpers for tests of all crates, enabled by `test-util` feature
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+pub use tempfile::TempDir;
+
+/// Create unique temporary directory, removed with its contents when dropped
+pub fn temp_dir() -> TempDir {
+    tempfile::Builder::new()
+        .prefix("foobar-test-")
+        .tempdir()
+        .expect("temporary directory should be created")
+}
+
+/// Create temporary directory with given files
+///
+/// File paths are relative to the directory, and may include
+/// subdirectories, which are created as needed.
+pub fn temp_dir_with_files(files: &[(&str, &str)]) -> TempDir {
+    let dir = temp_dir();
+    for (path, contents) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().expect("file path should have a parent"))
+            .expect("directory should be created");
+        std::fs::write(path, contents).expect("file should be written");
+    }
+    dir
+}
+
+type Labels = Vec<(String, String)>;
+
+/// Metrics captured by recorder
+///
+/// Taking a snapshot resets all values in the recorder, so metrics
+/// recorded by the same code should be checked on a single snapshot.
+pub struct MetricsSnapshot(Vec<(String, Labels, DebugValue)>);
+
+impl MetricsSnapshot {
+    pub fn take(recorder: &DebuggingRecorder) -> Self {
+        Self(
+            recorder
+                .snapshotter()
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .map(|(key, .., value)| {
+                    let labels = key
+                        .key()
+                        .labels()
+                        .map(|label| (label.key().to_string(), label.value().to_string()))
+                        .collect();
+                    (key.key().name().to_string(), labels, value)
+                })
+                .collect(),
+        )
+    }
+
+    /// Values of counter `name`, one per label set
+    pub fn counter_values(&self, name: &str) -> Vec<u64> {
+        self.counters(name).map(|(_, count)| count).collect()
+    }
+
+    /// Values of counter `name` along with values of its `label`, sorted
+    pub fn counter_values_by_label(&self, name: &str, label: &str) -> Vec<(String, u64)> {
+        let mut values: Vec<_> = self
+            .counters(name)
+            .map(|(labels, count)| {
+                let value = labels
+                    .iter()
+                    .find_map(|(key, value)| (key == label).then(|| value.clone()))
+                    .unwrap_or_else(|| panic!("counter {name} should have label {label}"));
+                (value, count)
+            })
+            .collect();
+        values.sort();
+        values
+    }
+
+    /// Samples of histogram `name`, for all label sets
+    pub fn histogram_values(&self, name: &str) -> Vec<f64> {
+        self.metrics(name)
+            .flat_map(|(_, value)| {
+                let DebugValue::Histogram(samples) = value else {
+                    panic!("metric {name} should be a histogram");
+                };
+                samples.iter().map(|sample| sample.into_inner())
+            })
+            .collect()
+    }
+
+    fn metrics(&self, name: &str) -> impl Iterator<Item = (&[(String, String)], &DebugValue)> {
+        self.0
+            .iter()
+            .filter(move |(metric, ..)| metric == name)
+            .map(|(_, labels, value)| (labels.as_slice(), value))
+    }
+
+    fn counters(&self, name: &str) -> impl Iterator<Item = (&[(String, String)], u64)> {
+        self.metrics(name).map(move |(labels, value)| {
+            let DebugValue::Counter(count) = value else {
+                panic!("metric {name} should be a counter");
+            };
+            (labels, *count)
+        })
+    }
+}
+
+/// Values of counter `name` captured by `recorder`, one per label set
+pub fn counter_values(recorder: &DebuggingRecorder, name: &str) -> Vec<u64> {
+    MetricsSnapshot::take(recorder).counter_values(name)
+}
+
+/// Values of counter `name` captured by `recorder`, along with values
+/// of its `label`, sorted
+pub fn counter_values_by_label(
+    recorder: &DebuggingRecorder,
+    name: &str,
+    label: &str,
+) -> Vec<(String, u64)> {
+    MetricsSnapshot::take(recorder).counter_values_by_label(name, label)
+}
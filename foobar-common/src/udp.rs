@@ -0,0 +1,21 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::net::{SocketAddr, UdpSocket};
+
+/// Create UDP socket connected to given address
+///
+/// The socket is bound to wildcard address of the same family, with
+/// ephemeral port.
+pub fn connect_udp(addr: SocketAddr) -> std::io::Result<UdpSocket> {
+    let bind_addr: SocketAddr = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    }
+    .parse()
+    .expect("wildcard address should be parsable");
+    let socket = UdpSocket::bind(bind_addr)?;
+    socket.connect(addr)?;
+    Ok(socket)
+}
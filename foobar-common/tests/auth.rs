@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use foobar_common::{check_api_key, check_basic_auth, check_bearer_token, hash_api_key};
+
+#[test]
+fn test_check_bearer_token() {
+    assert!(check_bearer_token(Some("Bearer secret"), "secret"));
+    assert!(!check_bearer_token(Some("Bearer secreT"), "secret"));
+    assert!(!check_bearer_token(Some("Bearer secret1"), "secret"));
+    assert!(!check_bearer_token(Some("Bearer "), "secret"));
+    assert!(!check_bearer_token(Some("Basic secret"), "secret"));
+    assert!(!check_bearer_token(Some("secret"), "secret"));
+    assert!(!check_bearer_token(None, "secret"));
+}
+
+#[test]
+fn test_check_basic_auth() {
+    // "user:secret"
+    let credentials = "dXNlcjpzZWNyZXQ=";
+    assert!(check_basic_auth(
+        Some("Basic dXNlcjpzZWNyZXQ="),
+        credentials
+    ));
+    assert!(!check_basic_auth(
+        Some("Basic dXNlcjpzZWNyZXQ"),
+        credentials
+    ));
+    assert!(!check_basic_auth(
+        Some("Bearer dXNlcjpzZWNyZXQ="),
+        credentials
+    ));
+    assert!(!check_basic_auth(Some("Basic "), credentials));
+    assert!(!check_basic_auth(None, credentials));
+}
+
+#[test]
+fn test_hash_api_key() {
+    assert_eq!(
+        hash_api_key("secret"),
+        "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+    );
+}
+
+#[test]
+fn test_check_api_key() {
+    let hashes = vec![hash_api_key("first"), hash_api_key("second")];
+    assert!(check_api_key("first", &hashes));
+    assert!(check_api_key("second", &hashes));
+    assert!(!check_api_key("third", &hashes));
+    assert!(!check_api_key("first", &[]));
+}
@@ -0,0 +1,25 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use foobar_common::{Clock, MockClock};
+
+#[tokio::test]
+async fn test_mock_clock() {
+    let start: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+    let clock = MockClock::new(start);
+    let start_instant = clock.instant();
+    assert_eq!(clock.now(), start);
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(clock.now().to_rfc3339(), "2026-01-01T00:00:10+00:00");
+    assert_eq!(clock.instant() - start_instant, Duration::from_secs(10));
+
+    // sleeping completes right away, moving the clock forward
+    clock.sleep(Duration::from_secs(3600)).await;
+    assert_eq!(clock.now().to_rfc3339(), "2026-01-01T01:00:10+00:00");
+    assert_eq!(clock.elapsed(), Duration::from_secs(3610));
+}
@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use foobar_common::spawn_metrics_collector;
+
+#[tokio::test(start_paused = true)]
+async fn test_collector_restart() {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let collector = spawn_metrics_collector(Duration::from_secs(5), {
+        let calls = calls.clone();
+        move || {
+            if calls.fetch_add(1, Ordering::Relaxed) == 0 {
+                panic!("collector failure");
+            }
+        }
+    });
+
+    tokio::time::timeout(Duration::from_secs(60), async {
+        while calls.load(Ordering::Relaxed) < 3 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("collector should be restarted after panic");
+
+    assert!(!collector.is_finished());
+    collector.abort();
+}
@@ -0,0 +1,199 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::{DateTime, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use serde::Deserialize;
+use serde::de::value::StrDeserializer;
+
+use foobar_common::CronSchedule;
+
+/// Time zone with DST, switching between +01:00 and +02:00 at 01:00 UTC
+/// on 2026-03-29 and 2026-10-25, like Central European Time
+#[derive(Clone, Copy, Debug)]
+struct DstTz;
+
+impl DstTz {
+    const WINTER: FixedOffset = FixedOffset::east_opt(3600).unwrap();
+    const SUMMER: FixedOffset = FixedOffset::east_opt(7200).unwrap();
+}
+
+impl TimeZone for DstTz {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        DstTz
+    }
+
+    fn offset_from_local_date(&self, _: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+        unimplemented!()
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+        let valid =
+            |offset: FixedOffset| self.offset_from_utc_datetime(&(*local - offset)) == offset;
+        match (valid(Self::SUMMER), valid(Self::WINTER)) {
+            (true, true) => MappedLocalTime::Ambiguous(Self::SUMMER, Self::WINTER),
+            (true, false) => MappedLocalTime::Single(Self::SUMMER),
+            (false, true) => MappedLocalTime::Single(Self::WINTER),
+            (false, false) => MappedLocalTime::None,
+        }
+    }
+
+    fn offset_from_utc_date(&self, _: &NaiveDate) -> FixedOffset {
+        unimplemented!()
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let utc = utc.and_utc();
+        if utc >= utc_time("2026-03-29T01:00:00Z") && utc < utc_time("2026-10-25T01:00:00Z") {
+            Self::SUMMER
+        } else {
+            Self::WINTER
+        }
+    }
+}
+
+fn utc_time(time: &str) -> DateTime<Utc> {
+    time.parse().unwrap()
+}
+
+fn schedule(expression: &str) -> CronSchedule {
+    expression.parse().unwrap()
+}
+
+/// Run times of the schedule, starting from given time
+fn runs<Tz: TimeZone>(schedule: &CronSchedule, start: DateTime<Tz>, count: usize) -> Vec<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    std::iter::successors(schedule.next_after(&start), |time| {
+        schedule.next_after(time)
+    })
+    .take(count)
+    .map(|time| time.to_rfc3339())
+    .collect()
+}
+
+#[test]
+fn test_every_minute() {
+    assert_eq!(
+        runs(&schedule("* * * * *"), utc_time("2026-01-01T23:58:30Z"), 3),
+        vec![
+            "2026-01-01T23:59:00+00:00",
+            "2026-01-02T00:00:00+00:00",
+            "2026-01-02T00:01:00+00:00",
+        ]
+    );
+    // strictly after given time, even if it matches exactly
+    assert_eq!(
+        runs(&schedule("* * * * *"), utc_time("2026-01-01T00:00:00Z"), 1),
+        vec!["2026-01-01T00:01:00+00:00"]
+    );
+}
+
+#[test]
+fn test_fields() {
+    assert_eq!(
+        runs(
+            &schedule("*/20 9-10 * * *"),
+            utc_time("2026-01-01T10:30:00Z"),
+            3
+        ),
+        vec![
+            "2026-01-01T10:40:00+00:00",
+            "2026-01-02T09:00:00+00:00",
+            "2026-01-02T09:20:00+00:00",
+        ]
+    );
+    // 2026-01-01 is Thursday, 7 is Sunday same as 0
+    assert_eq!(
+        runs(
+            &schedule("0 12 * * 1,7"),
+            utc_time("2026-01-01T00:00:00Z"),
+            2
+        ),
+        vec!["2026-01-04T12:00:00+00:00", "2026-01-05T12:00:00+00:00"]
+    );
+    // either day of month or day of week matches
+    assert_eq!(
+        runs(&schedule("0 0 13 * 5"), utc_time("2026-02-01T00:00:00Z"), 3),
+        vec![
+            "2026-02-06T00:00:00+00:00",
+            "2026-02-13T00:00:00+00:00",
+            "2026-02-20T00:00:00+00:00",
+        ]
+    );
+    assert_eq!(
+        runs(&schedule("@monthly"), utc_time("2026-01-15T00:00:00Z"), 2),
+        vec!["2026-02-01T00:00:00+00:00", "2026-03-01T00:00:00+00:00"]
+    );
+    assert_eq!(
+        runs(&schedule("0 0 29 2 *"), utc_time("2026-01-01T00:00:00Z"), 1),
+        vec!["2028-02-29T00:00:00+00:00"]
+    );
+}
+
+#[test]
+fn test_dst_gap() {
+    // 02:00-03:00 local is skipped, so 02:30 run happens right after the gap
+    assert_eq!(
+        runs(
+            &schedule("30 2 * * *"),
+            DstTz.with_ymd_and_hms(2026, 3, 28, 12, 0, 0).unwrap(),
+            3
+        ),
+        vec![
+            "2026-03-29T03:00:00+02:00",
+            "2026-03-30T02:30:00+02:00",
+            "2026-03-31T02:30:00+02:00",
+        ]
+    );
+}
+
+#[test]
+fn test_dst_overlap() {
+    // 02:00-03:00 local happens twice, but is only matched once
+    assert_eq!(
+        runs(
+            &schedule("30 * * * *"),
+            DstTz.with_ymd_and_hms(2026, 10, 25, 1, 0, 0).unwrap(),
+            3
+        ),
+        vec![
+            "2026-10-25T01:30:00+02:00",
+            "2026-10-25T02:30:00+02:00",
+            "2026-10-25T03:30:00+01:00",
+        ]
+    );
+}
+
+#[test]
+fn test_invalid() {
+    for expression in [
+        "",
+        "* * * *",
+        "* * * * * *",
+        "60 * * * *",
+        "* 24 * * *",
+        "* * 0 * *",
+        "* * * 13 *",
+        "* * * * 8",
+        "5-1 * * * *",
+        "*/0 * * * *",
+        "foo * * * *",
+        "@often",
+        "0 0 31 2 *",
+    ] {
+        assert!(expression.parse::<CronSchedule>().is_err(), "{expression}");
+    }
+}
+
+#[test]
+fn test_serde() {
+    let deserialize =
+        |value| CronSchedule::deserialize(StrDeserializer::<serde::de::value::Error>::new(value));
+    assert_eq!(deserialize("@hourly").unwrap(), schedule("@hourly"));
+    assert!(deserialize("0 * * *").is_err());
+    assert_eq!(String::from(schedule(" @hourly ")), "@hourly");
+}
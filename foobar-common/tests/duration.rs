@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use foobar_common::{parse_seconds, parse_size};
+
+fn seconds(value: &str) -> Result<u64, String> {
+    parse_seconds(value).map(|seconds| seconds.get())
+}
+
+#[test]
+fn test_parse_seconds() {
+    assert_eq!(seconds("30"), Ok(30));
+    assert_eq!(seconds("30s"), Ok(30));
+    assert_eq!(seconds("5m"), Ok(300));
+    assert_eq!(seconds("2h"), Ok(7200));
+    assert_eq!(seconds("1d"), Ok(86400));
+    assert_eq!(seconds("1h30m"), Ok(5400));
+    assert_eq!(seconds(" 10s "), Ok(10));
+}
+
+#[test]
+fn test_parse_seconds_invalid() {
+    for value in [
+        "",
+        "0",
+        "0s",
+        "garbage",
+        "5x",
+        "s",
+        "m5",
+        "1.5s",
+        "-1s",
+        "500ms",
+        "5 m",
+        "99999999999999999999d",
+    ] {
+        assert!(
+            parse_seconds(value).is_err(),
+            "{value:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("1048576"), Ok(1048576));
+    assert_eq!(parse_size("512B"), Ok(512));
+    assert_eq!(parse_size("64KiB"), Ok(65536));
+    assert_eq!(parse_size("1MiB"), Ok(1048576));
+    assert_eq!(parse_size("2GiB"), Ok(2147483648));
+    assert_eq!(parse_size(" 4KiB "), Ok(4096));
+}
+
+#[test]
+fn test_parse_size_invalid() {
+    for value in [
+        "",
+        "0",
+        "0KiB",
+        "garbage",
+        "KiB",
+        "5x",
+        "1.5MiB",
+        "-1B",
+        "1 MiB",
+        "1KiB1B",
+        "99999999999999999999",
+        "99999999999999999GiB",
+    ] {
+        assert!(parse_size(value).is_err(), "{value:?} should be rejected");
+    }
+}
@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use sqlx::PgPool;
+
+use foobar_common::{MAX_ITEM_TEXT_LENGTH, is_valid_item_text};
+
+async fn insert_item(pool: &PgPool, text: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO items(text) VALUES($1)")
+        .bind(text)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[test]
+fn test_item_text_length() {
+    assert!(is_valid_item_text(""));
+    assert!(is_valid_item_text(&"a".repeat(MAX_ITEM_TEXT_LENGTH)));
+    assert!(!is_valid_item_text(&"a".repeat(MAX_ITEM_TEXT_LENGTH + 1)));
+    // counted in characters, not bytes
+    assert!(is_valid_item_text(&"я".repeat(MAX_ITEM_TEXT_LENGTH)));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_item_text_length_constraint(pool: PgPool) {
+    let text = "я".repeat(MAX_ITEM_TEXT_LENGTH);
+    assert!(is_valid_item_text(&text));
+    insert_item(&pool, &text).await.unwrap();
+
+    let text = "я".repeat(MAX_ITEM_TEXT_LENGTH + 1);
+    assert!(!is_valid_item_text(&text));
+    let err = insert_item(&pool, &text).await.unwrap_err();
+    // check_violation
+    assert_eq!(
+        err.as_database_error()
+            .and_then(|err| err.code())
+            .as_deref(),
+        Some("23514")
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_item_updated_at(pool: PgPool) {
+    sqlx::query("INSERT INTO items(text, created_at, updated_at) VALUES('foo', '1970-01-01 00:00:00+00', '1970-01-01 00:00:00+00')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE items SET text = 'bar'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (created_at_bumped, updated_at_bumped): (bool, bool) = sqlx::query_as(
+        "SELECT created_at > '1970-01-01 00:00:00+00', updated_at > '1970-01-01 00:00:00+00' FROM items",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(!created_at_bumped);
+    assert!(updated_at_bumped);
+}
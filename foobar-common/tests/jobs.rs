@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeSet;
+
+use sqlx::PgPool;
+
+use foobar_common::{claim_next, complete_job, enqueue_job};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_claim_complete(pool: PgPool) {
+    let id = enqueue_job(&pool, "foo", "bar").await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    let jobs = claim_next(&mut tx, 10).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, id);
+    assert_eq!(jobs[0].kind, "foo");
+    assert_eq!(jobs[0].payload, "bar");
+    complete_job(&mut tx, id).await.unwrap();
+    tx.commit().await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    assert!(claim_next(&mut tx, 10).await.unwrap().is_empty());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_rolled_back_claim(pool: PgPool) {
+    let id = enqueue_job(&pool, "foo", "").await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    assert_eq!(claim_next(&mut tx, 1).await.unwrap()[0].id, id);
+    tx.rollback().await.unwrap();
+
+    // job is available again if not completed
+    let mut tx = pool.begin().await.unwrap();
+    assert_eq!(claim_next(&mut tx, 1).await.unwrap()[0].id, id);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_concurrent_claimers(pool: PgPool) {
+    for _ in 0..4 {
+        enqueue_job(&pool, "foo", "").await.unwrap();
+    }
+
+    let mut first = pool.begin().await.unwrap();
+    let mut second = pool.begin().await.unwrap();
+
+    let first_ids: BTreeSet<i64> = claim_next(&mut first, 2)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|job| job.id)
+        .collect();
+    let second_ids: BTreeSet<i64> = claim_next(&mut second, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|job| job.id)
+        .collect();
+
+    assert_eq!(first_ids.len(), 2);
+    assert_eq!(second_ids.len(), 2);
+    assert!(first_ids.is_disjoint(&second_ids));
+}
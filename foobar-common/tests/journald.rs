@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+
+use tracing::{info, info_span};
+use tracing_subscriber::layer::SubscriberExt;
+
+use foobar_common::JournaldLayer;
+use foobar_common::testing::temp_dir;
+
+#[test]
+fn test_journald_layer() {
+    let dir = temp_dir();
+    let path = dir.path().join("socket");
+
+    let receiver = UnixDatagram::bind(&path).unwrap();
+    receiver
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    let subscriber = tracing_subscriber::registry()
+        .with(JournaldLayer::with_socket_path("foobar-test", &path).unwrap());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = info_span!("request", request_id = 42);
+        let _guard = span.enter();
+        info!(user.name = "foo", "hello\nworld");
+    });
+
+    let mut buf = [0u8; 4096];
+    let len = receiver.recv(&mut buf).unwrap();
+    let message = &buf[..len];
+    let contains = |needle: &[u8]| message.windows(needle.len()).any(|window| window == needle);
+
+    assert!(message.starts_with(b"PRIORITY=6\n"));
+    assert!(contains(b"\nSYSLOG_IDENTIFIER=foobar-test\n"));
+    assert!(contains(b"\nTARGET=journald\n"));
+    assert!(contains(b"\nREQUEST_ID=42\n"));
+    assert!(contains(b"\nUSER_NAME=foo\n"));
+    assert!(contains(b"\nMESSAGE\n\x0b\0\0\0\0\0\0\0hello\nworld\n"));
+}
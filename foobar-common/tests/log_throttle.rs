@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use foobar_common::{LogThrottle, MockClock};
+
+#[test]
+fn test_log_throttle() {
+    let clock = Arc::new(MockClock::new(Default::default()));
+    let mut throttle = LogThrottle::new(Duration::from_secs(60), clock.clone());
+
+    // errors every 5 seconds for 10 minutes
+    let mut logged = vec![];
+    for _ in 0..120 {
+        if let Some(suppressed) = throttle.event() {
+            logged.push(suppressed);
+        }
+        clock.advance(Duration::from_secs(5));
+    }
+    assert_eq!(logged, vec![0, 11, 11, 11, 11, 11, 11, 11, 11, 11]);
+
+    // recovery reports errors suppressed since the last logged one
+    assert_eq!(throttle.reset(), 11);
+    assert_eq!(throttle.reset(), 0);
+
+    // first error after recovery is logged right away
+    assert_eq!(throttle.event(), Some(0));
+    assert_eq!(throttle.event(), None);
+}
+
+#[test]
+fn test_log_throttle_period_expiry() {
+    let clock = Arc::new(MockClock::new(Default::default()));
+    let mut throttle = LogThrottle::new(Duration::from_secs(60), clock.clone());
+
+    assert_eq!(throttle.event(), Some(0));
+    clock.advance(Duration::from_secs(59));
+    assert_eq!(throttle.event(), None);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(throttle.event(), Some(1));
+}
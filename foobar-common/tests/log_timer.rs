@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::DateTime;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+
+use foobar_common::{DEFAULT_LOG_TIMESTAMP_FORMAT, LogTimer};
+
+fn format_time(timer: &LogTimer) -> String {
+    let mut output = String::new();
+    timer.format_time(&mut Writer::new(&mut output)).unwrap();
+    output
+}
+
+#[test]
+fn test_default_format() {
+    let timer = LogTimer::new(DEFAULT_LOG_TIMESTAMP_FORMAT, false).unwrap();
+    // e.g. 2026-01-01 12:00:00.000000
+    assert_eq!(format_time(&timer).len(), 26);
+}
+
+#[test]
+fn test_utc_rfc3339() {
+    let timer = LogTimer::new("%+", true).unwrap();
+    let output = format_time(&timer);
+    assert!(output.ends_with("+00:00"), "{output} should be in UTC");
+    assert!(DateTime::parse_from_rfc3339(&output).is_ok());
+}
+
+#[test]
+fn test_invalid_format() {
+    assert!(LogTimer::new("%F %Q", false).is_err());
+    assert!(LogTimer::new("%", true).is_err());
+}
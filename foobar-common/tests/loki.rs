@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use metrics_util::debugging::DebuggingRecorder;
+use tracing::info;
+use tracing_subscriber::layer::SubscriberExt;
+use url::Url;
+
+use foobar_common::loki_layer;
+use foobar_common::testing::counter_values;
+
+/// Read single HTTP request and return its body
+fn read_request(stream: &mut impl Read) -> String {
+    let mut data = vec![];
+    let mut buf = [0u8; 4096];
+    loop {
+        let len = stream.read(&mut buf).unwrap();
+        assert!(len > 0, "connection closed before request was complete");
+        data.extend_from_slice(&buf[..len]);
+        let request = String::from_utf8_lossy(&data);
+        if let Some((headers, body)) = request.split_once("\r\n\r\n") {
+            let content_length: usize = headers
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|value| value.trim().parse().unwrap())
+                })
+                .unwrap();
+            if body.len() >= content_length {
+                return body.to_string();
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_loki_drops_oldest_when_blocked() {
+    let recorder = DebuggingRecorder::new();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+
+    // first push fails, as if loki were down, the retry succeeds
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for status in ["500 Internal Server Error", "204 No Content"] {
+            let (mut stream, _) = listener.accept().unwrap();
+            let body = read_request(&mut stream);
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nconnection: close\r\ncontent-length: 0\r\n\r\n"
+            )
+            .unwrap();
+            sender.send(body).unwrap();
+        }
+    });
+
+    let (layer, task) = loki_layer("foobar-test", &url, 3).unwrap();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    // task is not running yet, so nothing is consumed from the queue
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..5 {
+            info!("event {i}");
+        }
+    });
+    assert_eq!(
+        counter_values(&recorder, "foobar_loki_dropped_logs_total"),
+        vec![2]
+    );
+
+    let task = tokio::spawn(task.run());
+    let mut bodies = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while bodies.len() < 2 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            bodies.extend(receiver.try_iter());
+        }
+    })
+    .await
+    .expect("loki push should be retried");
+    task.abort();
+
+    // failed batch is retried as is, with only the newest events kept
+    assert_eq!(bodies[0], bodies[1]);
+    let push: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+    let stream = &push["streams"][0];
+    assert_eq!(stream["stream"]["service"], "foobar-test");
+    assert_eq!(stream["stream"]["level"], "info");
+    let lines: Vec<&str> = stream["values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| value[1].as_str().unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].ends_with("event 2"));
+    assert!(lines[2].ends_with("event 4"));
+}
@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::{Duration, Instant};
+
+use sqlx::PgPool;
+use sqlx::migrate::{Migrate, Migration, MigrationType};
+
+use foobar_common::{
+    AppliedMigration, MIGRATOR, MigrationState, compare_migrations, get_migration_status,
+    run_migrations, verify_migrations,
+};
+
+fn migration(version: i64, sql: &'static str) -> Migration {
+    Migration::new(
+        version,
+        "test".into(),
+        MigrationType::Simple,
+        sql.into(),
+        false,
+    )
+}
+
+fn applied(migration: &Migration) -> AppliedMigration {
+    AppliedMigration {
+        version: migration.version,
+        description: migration.description.to_string(),
+        checksum: migration.checksum.to_vec(),
+        success: true,
+    }
+}
+
+fn states(known: &[Migration], applied: &[AppliedMigration]) -> Vec<(i64, MigrationState)> {
+    compare_migrations(known, applied)
+        .into_iter()
+        .map(|status| (status.version, status.state))
+        .collect()
+}
+
+#[test]
+fn test_compare_migrations() {
+    let known = [migration(1, "SELECT 1"), migration(2, "SELECT 2")];
+
+    assert_eq!(
+        states(&known, &[]),
+        vec![(1, MigrationState::Pending), (2, MigrationState::Pending)]
+    );
+    assert_eq!(
+        states(&known, &[applied(&known[0])]),
+        vec![(1, MigrationState::Applied), (2, MigrationState::Pending)]
+    );
+    assert_eq!(
+        states(&known, &[applied(&known[0]), applied(&known[1])]),
+        vec![(1, MigrationState::Applied), (2, MigrationState::Applied)]
+    );
+}
+
+#[test]
+fn test_compare_migrations_mismatch() {
+    let known = [migration(1, "SELECT 1")];
+    let modified = migration(1, "SELECT 11");
+
+    assert_eq!(
+        states(&known, &[applied(&modified)]),
+        vec![(1, MigrationState::ChecksumMismatch)]
+    );
+}
+
+#[test]
+fn test_compare_migrations_failed() {
+    let known = [migration(1, "SELECT 1")];
+    let failed = AppliedMigration {
+        success: false,
+        ..applied(&known[0])
+    };
+
+    assert_eq!(states(&known, &[failed]), vec![(1, MigrationState::Failed)]);
+}
+
+#[test]
+fn test_compare_migrations_unknown() {
+    let known = [migration(1, "SELECT 1")];
+    let newer = migration(2, "SELECT 2");
+
+    assert_eq!(
+        states(&known, &[applied(&known[0]), applied(&newer)]),
+        vec![(1, MigrationState::Applied), (2, MigrationState::Unknown)]
+    );
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_migration_status_empty(pool: PgPool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let status = get_migration_status(&mut conn).await.unwrap();
+    assert_eq!(status.len(), MIGRATOR.iter().count());
+    assert!(
+        status
+            .iter()
+            .all(|status| status.state == MigrationState::Pending)
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_migration_status_applied(pool: PgPool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let status = get_migration_status(&mut conn).await.unwrap();
+    assert_eq!(status.len(), MIGRATOR.iter().count());
+    assert!(!status.iter().any(|status| status.is_problem()));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_migration_status_mismatch(pool: PgPool) {
+    sqlx::query("UPDATE _sqlx_migrations SET checksum = '\\x00' WHERE version = 1")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    let status = get_migration_status(&mut conn).await.unwrap();
+    assert_eq!(status[0].version, 1);
+    assert_eq!(status[0].state, MigrationState::ChecksumMismatch);
+    assert!(status[0].is_problem());
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_run_migrations(pool: PgPool) {
+    run_migrations(&pool, Duration::from_secs(1)).await.unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    let status = get_migration_status(&mut conn).await.unwrap();
+    assert!(!status.iter().any(|status| status.is_problem()));
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_run_migrations_lock_held(pool: PgPool) {
+    // same lock as taken by another instance running migrations
+    let mut holder = pool.acquire().await.unwrap();
+    holder.lock().await.unwrap();
+
+    let start = Instant::now();
+    let err = run_migrations(&pool, Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(format!("{err:#}").contains("another instance is holding the migration lock"));
+
+    holder.unlock().await.unwrap();
+    run_migrations(&pool, Duration::from_millis(100))
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_verify_migrations(pool: PgPool) {
+    verify_migrations(&pool).await.unwrap();
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_verify_migrations_pending(pool: PgPool) {
+    let err = verify_migrations(&pool).await.unwrap_err();
+    assert!(format!("{err:#}").contains("1 (pending)"));
+
+    // nothing was created
+    let (table_exists,): (bool,) =
+        sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!table_exists);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_item_text_unique_removes_duplicates(pool: PgPool) {
+    let mut conn = pool.acquire().await.unwrap();
+    conn.ensure_migrations_table().await.unwrap();
+    let (before, after): (Vec<_>, Vec<_>) = MIGRATOR
+        .iter()
+        .filter(|migration| migration.version <= 4)
+        .partition(|migration| migration.version < 4);
+    for migration in before {
+        conn.apply(migration).await.unwrap();
+    }
+
+    sqlx::query("INSERT INTO items(text) VALUES('foo'), ('bar'), ('foo'), ('foo')")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    conn.apply(after[0]).await.unwrap();
+
+    let items: Vec<(i32, String)> = sqlx::query_as("SELECT id, text FROM items ORDER BY id")
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(items, vec![(1, "foo".to_string()), (2, "bar".to_string())]);
+}
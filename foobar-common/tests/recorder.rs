@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use metrics::counter;
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+use foobar_common::install_recorder;
+
+// the only test in this binary, as global recorder can only be installed once
+#[test]
+fn test_install_recorder_with_prefix() {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let handle = recorder.handle();
+    install_recorder(recorder, Some("myservice")).unwrap();
+
+    counter!("foobar_test_total").increment(1);
+
+    let rendered = handle.render();
+    assert!(
+        rendered.contains("myservice_foobar_test_total 1"),
+        "{rendered}"
+    );
+    assert!(install_recorder(PrometheusBuilder::new().build_recorder(), None).is_err());
+}
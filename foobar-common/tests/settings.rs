@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+
+use foobar_common::{MockClock, SettingsCache, get_setting, set_setting};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_set_get(pool: PgPool) {
+    assert_eq!(get_setting::<bool>(&pool, "foo").await.unwrap(), None);
+
+    set_setting(&pool, "foo", &true).await.unwrap();
+    assert_eq!(get_setting::<bool>(&pool, "foo").await.unwrap(), Some(true));
+
+    set_setting(&pool, "foo", &false).await.unwrap();
+    assert_eq!(
+        get_setting::<bool>(&pool, "foo").await.unwrap(),
+        Some(false)
+    );
+
+    set_setting(&pool, "bar", &42).await.unwrap();
+    assert_eq!(get_setting::<u32>(&pool, "bar").await.unwrap(), Some(42));
+    assert_eq!(
+        get_setting::<bool>(&pool, "foo").await.unwrap(),
+        Some(false)
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_invalid_value(pool: PgPool) {
+    set_setting(&pool, "foo", &"yes").await.unwrap();
+    let err = get_setting::<bool>(&pool, "foo").await.unwrap_err();
+    assert!(err.to_string().contains("invalid value of setting \"foo\""));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_cache_expires(pool: PgPool) {
+    let clock = MockClock::new(Default::default());
+    let cache = SettingsCache::new(Duration::from_secs(5));
+
+    assert_eq!(cache.get::<u32>(&clock, &pool, "foo").await.unwrap(), None);
+    // change by another instance is not seen until cached value expires
+    set_setting(&pool, "foo", &1).await.unwrap();
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(cache.get::<u32>(&clock, &pool, "foo").await.unwrap(), None);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(
+        cache.get::<u32>(&clock, &pool, "foo").await.unwrap(),
+        Some(1)
+    );
+
+    // not read from the database while cached
+    sqlx::query("DELETE FROM settings")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        cache.get::<u32>(&clock, &pool, "foo").await.unwrap(),
+        Some(1)
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_cache_write_through(pool: PgPool) {
+    let clock = MockClock::new(Default::default());
+    let cache = SettingsCache::new(Duration::from_secs(5));
+
+    assert_eq!(cache.get::<u32>(&clock, &pool, "foo").await.unwrap(), None);
+    cache.set(&clock, &pool, "foo", &2).await.unwrap();
+    assert_eq!(
+        cache.get::<u32>(&clock, &pool, "foo").await.unwrap(),
+        Some(2)
+    );
+    assert_eq!(get_setting::<u32>(&pool, "foo").await.unwrap(), Some(2));
+}
@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::net::UdpSocket;
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+
+use foobar_common::StatsdRecorder;
+
+#[test]
+fn test_statsd_recorder() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    receiver
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    let recorder = StatsdRecorder::new(receiver.local_addr().unwrap()).unwrap();
+
+    metrics::with_local_recorder(&recorder, || {
+        counter!("foobar_requests_total", "method" => "GET", "status" => "200").increment(2);
+        counter!("foobar_events_total").absolute(10);
+        counter!("foobar_events_total").absolute(15);
+        gauge!("foobar_connections").set(3.0);
+        gauge!("foobar_connections").increment(1.5);
+        histogram!("foobar_duration_seconds").record(0.25);
+    });
+
+    let mut buf = [0u8; 1024];
+    let mut received = vec![];
+    for _ in 0..6 {
+        let len = receiver.recv(&mut buf).unwrap();
+        received.push(String::from_utf8(buf[..len].to_vec()).unwrap());
+    }
+    assert_eq!(
+        received,
+        vec![
+            "foobar_requests_total:2|c|#method:GET,status:200",
+            "foobar_events_total:10|c",
+            "foobar_events_total:5|c",
+            "foobar_connections:3|g",
+            "foobar_connections:4.5|g",
+            "foobar_duration_seconds:0.25|h",
+        ]
+    );
+}
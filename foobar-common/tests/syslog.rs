@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::net::UdpSocket;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::{info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+
+use foobar_common::{SyslogFacility, syslog_layer};
+
+#[test]
+fn test_facility() {
+    assert_eq!("daemon".parse(), Ok(SyslogFacility::Daemon));
+    assert_eq!("local7".parse(), Ok(SyslogFacility::Local7));
+    assert!("nonexistent".parse::<SyslogFacility>().is_err());
+}
+
+#[test]
+fn test_syslog_layer() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    receiver
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    let output = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let fmt_layer = tracing_subscriber::fmt::Layer::new()
+        .with_ansi(false)
+        .with_writer({
+            let output = output.clone();
+            move || OutputWriter(output.clone())
+        });
+
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            syslog_layer(
+                "foobar-test",
+                SyslogFacility::Local0,
+                Some(receiver.local_addr().unwrap()),
+            )
+            .unwrap(),
+        )
+        .with(fmt_layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        info!(request_id = 42, "hello");
+        warn!("careful");
+    });
+
+    let mut buf = [0u8; 1024];
+    let mut received = vec![];
+    for _ in 0..2 {
+        let len = receiver.recv(&mut buf).unwrap();
+        received.push(String::from_utf8(buf[..len].to_vec()).unwrap());
+    }
+    let pid = std::process::id();
+
+    // local0 is 16, info is 6, warning is 4
+    assert!(received[0].starts_with("<134>1 "));
+    assert!(received[0].ends_with(&format!(
+        " foobar-test {pid} - - syslog: hello request_id=42"
+    )));
+    assert!(received[1].starts_with("<132>1 "));
+    assert!(received[1].ends_with(&format!(" foobar-test {pid} - - syslog: careful")));
+
+    // other layers still work
+    let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("INFO syslog: hello request_id=42"));
+    assert!(output.contains("WARN syslog: careful"));
+}
+
+struct OutputWriter(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for OutputWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
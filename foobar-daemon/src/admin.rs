@@ -0,0 +1,325 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+
+use foobar_common::{audit, check_bearer_token};
+
+use crate::worker::{PauseFlag, WorkerHealth};
+
+/// Audit actor for requests authorized with the admin token
+const ADMIN_TOKEN_ACTOR: &str = "admin_token";
+
+struct AdminState {
+    token: String,
+    pool: PgPool,
+    paused: PauseFlag,
+}
+
+struct ReadinessState {
+    health: WorkerHealth,
+    stale_after: Duration,
+}
+
+async fn auth_middleware(
+    State(state): State<Arc<AdminState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+
+    if check_bearer_token(authorization, &state.token) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response()
+    }
+}
+
+async fn set_paused(state: &AdminState, paused: bool) -> Response {
+    let result = state.paused.persist(&state.pool, paused).await;
+    audit!(
+        actor = ADMIN_TOKEN_ACTOR,
+        action = if paused {
+            "pause_workers"
+        } else {
+            "resume_workers"
+        },
+        target = "workers",
+        result = if result.is_ok() { "success" } else { "failure" },
+    );
+    match result {
+        Ok(()) if paused => "paused".into_response(),
+        Ok(()) => "resumed".into_response(),
+        Err(error) => {
+            error!(%error, "cannot persist worker pause state");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "cannot persist worker pause state",
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn pause(State(state): State<Arc<AdminState>>) -> Response {
+    info!("pausing workers");
+    set_paused(&state, true).await
+}
+
+async fn resume(State(state): State<Arc<AdminState>>) -> Response {
+    info!("resuming workers");
+    set_paused(&state, false).await
+}
+
+/// Report whether all workers have succeeded recently
+async fn ready(State(state): State<Arc<ReadinessState>>) -> Response {
+    let stale_workers = state.health.stale_workers(state.stale_after);
+    if stale_workers.is_empty() {
+        "ready".into_response()
+    } else {
+        let stale_workers = stale_workers.join(", ");
+        warn!(%stale_workers, "workers have not succeeded recently");
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("stale workers: {stale_workers}"),
+        )
+            .into_response()
+    }
+}
+
+/// Create router for daemon administrative endpoints
+///
+/// All endpoints except `/ready` require `Authorization: Bearer
+/// <token>` header. `/ready` responds with 503 if any worker has not
+/// succeeded within `stale_after`. Pause state is persisted in
+/// `pool`, so it survives restarts.
+pub fn admin_router(
+    token: String,
+    pool: PgPool,
+    paused: PauseFlag,
+    health: WorkerHealth,
+    stale_after: Duration,
+) -> Router {
+    let state = Arc::new(AdminState {
+        token,
+        pool,
+        paused,
+    });
+    Router::new()
+        .route("/admin/worker/pause", post(pause))
+        .route("/admin/worker/resume", post(resume))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ))
+        .with_state(state)
+        .merge(
+            Router::new()
+                .route("/ready", get(ready))
+                .with_state(Arc::new(ReadinessState {
+                    health,
+                    stale_after,
+                })),
+        )
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::time::Duration;
+
+    use axum_test::TestServer;
+
+    use foobar_common::MockClock;
+
+    use super::*;
+    use crate::worker::{Worker, WorkerRegistry};
+    use crate::workers::ItemChurnWorker;
+
+    async fn count_items(pool: &PgPool) -> i64 {
+        let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        count
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_pause_resume(pool: PgPool) {
+        let registry = WorkerRegistry::default().register(ItemChurnWorker);
+        let server = TestServer::new(admin_router(
+            "secret".into(),
+            pool.clone(),
+            registry.pause_flag(),
+            registry.health(),
+            Duration::from_secs(300),
+        ));
+
+        server
+            .post("/admin/worker/pause")
+            .authorization_bearer("secret")
+            .await
+            .assert_status_ok();
+
+        let mut tasks = registry
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+
+        // item churn worker adds an item on its first tick right away
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(count_items(&pool).await, 0);
+
+        server
+            .post("/admin/worker/resume")
+            .authorization_bearer("secret")
+            .await
+            .assert_status_ok();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while count_items(&pool).await == 0 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("worker should modify items after resume");
+
+        tasks.abort_all();
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_pause_persisted(pool: PgPool) {
+        let server = TestServer::new(admin_router(
+            "secret".into(),
+            pool.clone(),
+            Default::default(),
+            Default::default(),
+            Duration::from_secs(300),
+        ));
+
+        // pause state is picked up after restart or by another instance
+        let restarted = PauseFlag::default();
+        server
+            .post("/admin/worker/pause")
+            .authorization_bearer("secret")
+            .await
+            .assert_status_ok();
+        restarted.load(&pool).await.unwrap();
+        assert!(restarted.is_paused());
+
+        server
+            .post("/admin/worker/resume")
+            .authorization_bearer("secret")
+            .await
+            .assert_status_ok();
+        restarted.load(&pool).await.unwrap();
+        assert!(!restarted.is_paused());
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized() {
+        let paused = PauseFlag::default();
+        let pool = PgPool::connect_lazy("postgresql://localhost:1/nonexistent").unwrap();
+        let server = TestServer::new(admin_router(
+            "secret".into(),
+            pool,
+            paused.clone(),
+            Default::default(),
+            Duration::from_secs(300),
+        ));
+
+        let response = server.post("/admin/worker/pause").await;
+        response.assert_status_unauthorized();
+        response.assert_header("www-authenticate", "Bearer");
+
+        let response = server
+            .post("/admin/worker/pause")
+            .authorization_bearer("wrong")
+            .await;
+        response.assert_status_unauthorized();
+
+        assert!(!paused.is_paused());
+    }
+
+    struct FailingWorker;
+
+    impl Worker for FailingWorker {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_millis(10)
+        }
+
+        async fn tick(&self, _: &PgPool) -> anyhow::Result<()> {
+            anyhow::bail!("always fails")
+        }
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_ready(pool: PgPool) {
+        let registry = WorkerRegistry::default().register(ItemChurnWorker);
+        let server = TestServer::new(admin_router(
+            "secret".into(),
+            pool.clone(),
+            registry.pause_flag(),
+            registry.health(),
+            Duration::from_secs(300),
+        ));
+        let mut tasks = registry
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+
+        // no authorization required
+        let response = server.get("/ready").await;
+        response.assert_status_ok();
+        response.assert_text("ready");
+
+        tasks.abort_all();
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_not_ready_when_stale(pool: PgPool) {
+        let clock = Arc::new(MockClock::new(Default::default()));
+        let registry = WorkerRegistry::default()
+            .with_clock(clock.clone())
+            .register(FailingWorker);
+        let server = TestServer::new(admin_router(
+            "secret".into(),
+            pool.clone(),
+            registry.pause_flag(),
+            registry.health(),
+            Duration::from_secs(300),
+        ));
+        let mut tasks = registry
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+
+        // within the window after spawn
+        server.get("/ready").await.assert_status_ok();
+
+        clock.advance(Duration::from_secs(301));
+        let response = server.get("/ready").await;
+        response.assert_status_service_unavailable();
+        response.assert_text("stale workers: failing");
+
+        tasks.abort_all();
+    }
+}
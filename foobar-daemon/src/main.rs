@@ -1,20 +1,46 @@
 // SPDX-FileCopyrightText: Copyright 2025 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-#![feature(try_blocks)]
+#![cfg_attr(test, feature(coverage_attribute))]
 
+mod admin;
 mod config;
+mod pidfile;
+mod reopenable_writer;
+mod worker;
+mod workers;
 
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::time::Duration;
 
-use anyhow::Context as _;
-use indoc::indoc;
+use anyhow::{Context as _, anyhow};
 use metrics::{counter, gauge};
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Executor, PgPool};
-use tracing::{error, info};
-
-use crate::config::Config;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use sqlx::PgPool;
+use tokio::runtime::Runtime;
+use tracing::{Subscriber, error, info};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::{LevelFilter, filter_fn};
+use tracing_subscriber::registry::LookupSpan;
+use url::Url;
+
+use foobar_common::{
+    DEFAULT_LOKI_QUEUE_SIZE, JournaldLayer, LogTimer, PoolConfig, SCHEMA, StatsdRecorder, connect,
+    describe_dsn, get_migration_status, install_recorder, is_audit_event, loki_layer, redact_url,
+    run_migrations, spawn_metrics_collector, spawn_pool_health_probe, syslog_layer,
+};
+
+use crate::admin::admin_router;
+use crate::config::{Command, Config};
+use crate::pidfile::PidFile;
+use crate::reopenable_writer::ReopenableWriter;
+use crate::worker::WorkerRegistry;
+use crate::workers::ItemChurnWorker;
+
+/// Interval of collecting process and runtime metrics, and of pushing
+/// metrics to Pushgateway
+const METRICS_INTERVAL: Duration = Duration::from_secs(5);
 
 #[allow(unexpected_cfgs)]
 fn collect_tokio_runtime_metrics() {
@@ -70,7 +96,6 @@ fn collect_tokio_runtime_metrics() {
 }
 
 fn init_logging(config: &Config) -> anyhow::Result<()> {
-    use tracing_subscriber::Layer;
     use tracing_subscriber::filter::EnvFilter;
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
@@ -80,29 +105,56 @@ fn init_logging(config: &Config) -> anyhow::Result<()> {
     let mut layers = vec![];
 
     if let Some(loki_url) = &config.loki_url {
-        let (layer, task) = tracing_loki::builder()
-            .label("service", "foobar-daemon")?
-            .build_url(loki_url.clone())
+        let (layer, task) = loki_layer("foobar-daemon", loki_url, DEFAULT_LOKI_QUEUE_SIZE)
             .context("loki logging initialization failed")?;
-        tokio::spawn(task);
-        layers.push(layer.boxed());
+        tokio::spawn(task.run());
+        layers.push(layer);
     }
 
-    let layer = tracing_subscriber::fmt::Layer::new().with_timer(
-        tracing_subscriber::fmt::time::ChronoLocal::new(String::from("%F %T%.6f")),
-    );
+    if config.syslog {
+        layers.push(
+            syslog_layer("foobar-daemon", config.syslog_facility, config.syslog_addr)
+                .context("syslog logging initialization failed")?,
+        );
+    }
+
+    if config.journald {
+        layers.push(
+            JournaldLayer::new("foobar-daemon")
+                .context("journald logging initialization failed")?
+                .boxed(),
+        );
+    }
+
+    let timer = LogTimer::new(&config.log_timestamp_format, config.log_timestamp_utc)?;
 
     if let Some(log_directory) = &config.log_directory {
-        use tracing_appender::rolling::{RollingFileAppender, Rotation};
-        let logfile = RollingFileAppender::builder()
-            .rotation(Rotation::DAILY)
-            .filename_prefix("foobar-daemon.log")
-            .max_log_files(14)
-            .build(log_directory)
-            .context("logging initialization failed")?;
-        layers.push(layer.with_writer(logfile).boxed());
-    } else {
-        layers.push(layer.boxed());
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let (file_layers, logfiles) =
+            log_file_layers(log_directory, config.error_log, config.audit_log, timer)
+                .context("logging initialization failed")?;
+
+        // reopen log files on SIGHUP, to support external log rotation
+        let mut sighup = signal(SignalKind::hangup()).context("cannot install SIGHUP handler")?;
+        tokio::spawn(async move {
+            while sighup.recv().await.is_some() {
+                info!("reopening log files");
+                for logfile in &logfiles {
+                    if let Err(error) = logfile.reopen() {
+                        error!(%error, "cannot reopen log file");
+                    }
+                }
+            }
+        });
+
+        layers.extend(file_layers);
+    } else if config.log_to_stdout() {
+        layers.push(
+            tracing_subscriber::fmt::Layer::new()
+                .with_timer(timer)
+                .boxed(),
+        );
     }
 
     tracing_subscriber::registry()
@@ -113,118 +165,440 @@ fn init_logging(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+type LogFile = ReopenableWriter<tracing_appender::rolling::RollingFileAppender>;
+type BoxedLayer<S> = Box<dyn Layer<S> + Send + Sync>;
+
+fn open_log_file(log_directory: &Path, prefix: &'static str) -> anyhow::Result<LogFile> {
+    use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+    let log_directory = log_directory.to_path_buf();
+    ReopenableWriter::new(move || {
+        Ok(RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(prefix)
+            .max_log_files(14)
+            .build(&log_directory)?)
+    })
+}
+
+/// Create layers writing to log files in `log_directory`
+///
+/// Everything is written into the main log file, and if `error_log`
+/// or `audit_log` is set, errors or audit events respectively are
+/// additionally written into separate files.
+/// Log files are returned as well, to be reopened on SIGHUP.
+fn log_file_layers<S>(
+    log_directory: &Path,
+    error_log: bool,
+    audit_log: bool,
+    timer: LogTimer,
+) -> anyhow::Result<(Vec<BoxedLayer<S>>, Vec<LogFile>)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let logfile = open_log_file(log_directory, "foobar-daemon.log")?;
+    let mut layers = vec![
+        tracing_subscriber::fmt::Layer::new()
+            .with_timer(timer.clone())
+            .with_writer(logfile.clone())
+            .boxed(),
+    ];
+    let mut logfiles = vec![logfile];
+
+    if error_log {
+        let logfile = open_log_file(log_directory, "foobar-daemon.error.log")?;
+        layers.push(
+            tracing_subscriber::fmt::Layer::new()
+                .with_timer(timer.clone())
+                .with_writer(logfile.clone())
+                .with_filter(LevelFilter::ERROR)
+                .boxed(),
+        );
+        logfiles.push(logfile);
+    }
+
+    if audit_log {
+        let logfile = open_log_file(log_directory, "foobar-daemon.audit.log")?;
+        layers.push(
+            tracing_subscriber::fmt::Layer::new()
+                .with_timer(timer)
+                .with_writer(logfile.clone())
+                .with_filter(filter_fn(is_audit_event))
+                .boxed(),
+        );
+        logfiles.push(logfile);
+    }
+
+    Ok((layers, logfiles))
+}
+
+/// Configure exporter pushing metrics to Prometheus Pushgateway
+///
+/// Metrics are grouped by `job` and (optionally) `instance` labels,
+/// which are passed in the URL path. Credentials, if present in
+/// the URL, are used for basic authentication.
+fn push_gateway_exporter(
+    gateway: &Url,
+    job: &str,
+    instance: Option<&str>,
+    interval: Duration,
+) -> anyhow::Result<PrometheusBuilder> {
+    let mut endpoint = gateway.clone();
+    let username = Some(endpoint.username().to_string()).filter(|username| !username.is_empty());
+    let password = endpoint.password().map(str::to_string);
+    let _ = endpoint.set_username("");
+    let _ = endpoint.set_password(None);
+    {
+        let mut segments = endpoint
+            .path_segments_mut()
+            .map_err(|_| anyhow!("invalid Pushgateway URL {}", redact_url(gateway)))?;
+        segments.pop_if_empty().extend(["metrics", "job", job]);
+        if let Some(instance) = instance {
+            segments.extend(["instance", instance]);
+        }
+    }
+    // used by the exporter for HTTPS; fails if already installed, which is fine
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    Ok(PrometheusBuilder::new().with_push_gateway(
+        endpoint.as_str(),
+        interval,
+        username,
+        password,
+        true,
+    )?)
+}
+
 fn init_metrics(config: &Config) -> anyhow::Result<()> {
-    if let Some(socket_addr) = &config.prometheus_export {
+    let builder = if let Some(socket_addr) = &config.prometheus_export {
         info!("initializing prometheus exporter");
-        use metrics_exporter_prometheus::PrometheusBuilder;
+        Some(PrometheusBuilder::new().with_http_listener(*socket_addr))
+    } else if let Some(gateway) = &config.prometheus_push_gateway {
+        info!("initializing prometheus pushgateway exporter");
+        Some(push_gateway_exporter(
+            gateway,
+            &config.prometheus_push_job,
+            config.prometheus_push_instance.as_deref(),
+            METRICS_INTERVAL,
+        )?)
+    } else {
+        None
+    };
 
-        PrometheusBuilder::new()
-            .with_http_listener(*socket_addr)
-            .install()
+    if let Some(builder) = builder {
+        let (recorder, exporter) = builder
+            .build()
             .context("prometheus exporter initialization failed")?;
+        tokio::spawn(exporter);
+        install_recorder(recorder, config.metrics_prefix.as_deref())?;
+    } else if let Some(statsd_addr) = &config.statsd_addr {
+        info!("initializing statsd exporter");
+        let recorder =
+            StatsdRecorder::new(*statsd_addr).context("statsd exporter initialization failed")?;
+        install_recorder(recorder, config.metrics_prefix.as_deref())?;
+    } else {
+        return Ok(());
+    }
 
-        let collector = metrics_process::Collector::default();
-        collector.describe();
+    let collector = metrics_process::Collector::default();
+    collector.describe();
 
-        tokio::spawn(async move {
-            loop {
-                collector.collect();
-                collect_tokio_runtime_metrics();
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-            }
-        });
-    }
+    spawn_metrics_collector(METRICS_INTERVAL, move || {
+        collector.collect();
+        collect_tokio_runtime_metrics();
+    });
 
     Ok(())
 }
 
 async fn init_database(config: &Config) -> anyhow::Result<PgPool> {
     info!("initializing database pool");
-    let pool = PgPoolOptions::new()
-        .after_connect(|conn, _meta| {
-            Box::pin(async move {
-                conn.execute("SET application_name = 'foobar-daemon'")
-                    .await?;
-                Ok(())
-            })
+    connect(
+        &config.dsn,
+        &PoolConfig {
+            application_name: "foobar-daemon",
+            statement_cache_capacity: config.statement_cache_capacity,
+        },
+    )
+    .await
+}
+
+/// Log single event summarizing the effective configuration
+///
+/// Contains no secrets, so it's safe to ship to external log storage.
+fn log_startup_banner(config: &Config, registry: &WorkerRegistry) {
+    let workers: Vec<String> = registry
+        .intervals()
+        .map(|(name, interval)| {
+            let concurrency = config.worker_concurrency.get(name).map_or(1, |n| n.get());
+            match config.worker_schedule.get(name) {
+                Some(schedule) => format!("{name}x{concurrency}/({schedule})"),
+                None => format!("{name}x{concurrency}/{interval:?}"),
+            }
         })
-        .connect(&config.dsn)
-        .await
-        .context("error creating PostgreSQL connection pool")?;
+        .collect();
+    let features: Vec<&str> = [
+        (config.admin_listen.is_some(), "admin"),
+        (config.loki_url.is_some(), "loki"),
+        (config.syslog, "syslog"),
+        (config.journald, "journald"),
+        (config.prometheus_export.is_some(), "prometheus"),
+        (config.prometheus_push_gateway.is_some(), "pushgateway"),
+        (config.statsd_addr.is_some(), "statsd"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+
+    info!(
+        version = env!("CARGO_PKG_VERSION"),
+        admin_listen = config.admin_listen.map(|addr| addr.to_string()),
+        database = describe_dsn(&config.dsn),
+        schema = SCHEMA,
+        workers = workers.join(","),
+        features = features.join(","),
+        "starting foobar-daemon"
+    );
+}
 
-    Ok(pool)
+async fn check_migrations(pool: &PgPool) -> anyhow::Result<()> {
+    let mut conn = pool.acquire().await?;
+    let status = get_migration_status(&mut conn).await?;
+
+    for migration in &status {
+        println!(
+            "{} {}: {}",
+            migration.version,
+            migration.description,
+            migration.state.as_str()
+        );
+    }
+
+    let num_problems = status.iter().filter(|status| status.is_problem()).count();
+    if num_problems > 0 {
+        anyhow::bail!("{num_problems} migration(s) are not applied or do not match");
+    }
+    println!("all migrations are applied");
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut sigterm =
+        signal(SignalKind::terminate()).expect("SIGTERM handler should be installable");
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = sigterm.recv() => {}
+    }
+}
+
+fn build_runtime(worker_threads: Option<NonZeroUsize>) -> std::io::Result<Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+    if let Some(worker_threads) = worker_threads {
+        builder.worker_threads(worker_threads.get());
+    }
+    builder.build()
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     let config = Config::parse().with_context(|| "failed to process configuration")?;
 
+    build_runtime(config.worker_threads)
+        .with_context(|| "failed to build tokio runtime")?
+        .block_on(async_main(config))
+}
+
+async fn async_main(config: Config) -> anyhow::Result<()> {
+    if config.print_effective_config {
+        print!("{}", config.to_redacted_toml());
+        return Ok(());
+    }
+
+    if config.check_migrations {
+        let pool = init_database(&config)
+            .await
+            .with_context(|| "failed to init database")?;
+        return check_migrations(&pool).await;
+    }
+
     init_logging(&config).with_context(|| "failed to init logging")?;
+
+    if config.command == Some(Command::Migrate) {
+        let pool = init_database(&config)
+            .await
+            .with_context(|| "failed to init database")?;
+        info!("running migrations");
+        run_migrations(&pool, config.migration_lock_timeout()).await?;
+        info!("migrations complete");
+        return Ok(());
+    }
+
+    let registry = WorkerRegistry::default().register(ItemChurnWorker);
+    log_startup_banner(&config, &registry);
+
+    let _pid_file = config
+        .pid_file
+        .as_deref()
+        .map(PidFile::create)
+        .transpose()
+        .with_context(|| "failed to create PID file")?;
     init_metrics(&config).with_context(|| "failed to init metrics")?;
     let pool = init_database(&config)
         .await
         .with_context(|| "failed to init database")?;
+    spawn_pool_health_probe(pool.clone(), Duration::from_secs(10));
 
     info!("running migrations");
-    sqlx::query("CREATE SCHEMA IF NOT EXISTS foobar")
-        .execute(&pool)
-        .await
-        .context("failed to create schema")?;
-
-    foobar_common::MIGRATOR
-        .run(&pool)
-        .await
-        .context("failed to run migrations")?;
+    run_migrations(&pool, config.migration_lock_timeout()).await?;
 
     info!("running daemon");
-    let foobar_worker = {
-        let pool = pool.clone();
-        tokio::spawn(async move {
-            loop {
-                let res = try {
-                    let (num_items, random): (i64, f64) = sqlx::query_as(indoc! {"
-                        SELECT
-                            count(*), random()
-                        FROM items
-                    "})
-                    .fetch_one(&pool)
-                    .await?;
-
-                    if num_items < 10 || (num_items < 20 && random < 0.5) {
-                        let text = format!("{:x}", {
-                            use std::hash::{Hash, Hasher};
-                            let mut hasher = std::hash::DefaultHasher::new();
-                            random.to_bits().hash(&mut hasher);
-                            hasher.finish()
-                        });
-                        sqlx::query(indoc! {"
-                            INSERT INTO items(text)
-                            VALUES($1)
-                        "})
-                        .bind(&text)
-                        .execute(&pool)
-                        .await?;
-                    } else {
-                        sqlx::query(indoc! {"
-                            DELETE FROM items
-                            WHERE
-                                id = (SELECT min(id) FROM items)
-                        "})
-                        .execute(&pool)
-                        .await?;
-                    }
-                };
-
-                if let Err(error) = res {
-                    error!(%error, "error in foobar_worker");
-                }
 
-                tokio::time::sleep(Duration::from_secs(5)).await;
+    registry
+        .pause_flag()
+        .load(&pool)
+        .await
+        .with_context(|| "failed to load worker pause state")?;
+    registry.pause_flag().spawn_sync(pool.clone());
+
+    if let (Some(admin_listen), Some(admin_token)) = (&config.admin_listen, &config.admin_token) {
+        info!("serving admin endpoints");
+        let listener = tokio::net::TcpListener::bind(admin_listen)
+            .await
+            .with_context(|| format!("cannot listen on {admin_listen}"))?;
+        let router = admin_router(
+            admin_token.clone(),
+            pool.clone(),
+            registry.pause_flag(),
+            registry.health(),
+            config.worker_stale_after(),
+        );
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, router).await {
+                error!(%error, "admin server failed");
             }
-        })
-    };
+        });
+    }
+
+    let mut workers = registry
+        .spawn(&pool, &config.worker_concurrency, &config.worker_schedule)
+        .with_context(|| "failed to spawn workers")?;
 
-    foobar_worker.await?;
+    tokio::select! {
+        Some(res) = workers.join_next() => res?,
+        _ = shutdown_signal() => info!("shutting down"),
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_push_gateway() {
+        use axum::extract::Path;
+        use axum::routing::post;
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let gateway = axum::Router::new().route(
+            "/metrics/job/{job}/instance/{instance}",
+            post(
+                move |Path((job, instance)): Path<(String, String)>, body: String| async move {
+                    let _ = sender.send((job, instance, body));
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let gateway_url =
+            Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(axum::serve(listener, gateway).into_future());
+
+        let (recorder, exporter) = push_gateway_exporter(
+            &gateway_url,
+            "foobar-test",
+            Some("host1"),
+            Duration::from_millis(50),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            counter!("foobar_test_pushed_total").increment(1);
+        });
+        tokio::spawn(exporter);
+
+        let (job, instance, body) = tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+            .await
+            .expect("metrics should be pushed")
+            .unwrap();
+        assert_eq!(job, "foobar-test");
+        assert_eq!(instance, "host1");
+        assert!(body.contains("foobar_test_pushed_total 1"));
+    }
+
+    #[test]
+    fn test_error_log() {
+        use foobar_common::audit;
+        use foobar_common::testing::temp_dir;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let dir = temp_dir();
+        let (layers, _) = log_file_layers(
+            dir.path(),
+            true,
+            true,
+            LogTimer::new("%F %T", false).unwrap(),
+        )
+        .unwrap();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layers), || {
+            info!("informational event");
+            error!("error event");
+            audit!(
+                actor = "tester",
+                action = "test",
+                target = "nothing",
+                result = "success"
+            );
+        });
+
+        let read_log = |prefix: &str| -> String {
+            std::fs::read_dir(dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .starts_with(prefix)
+                })
+                .map(|path| std::fs::read_to_string(path).unwrap())
+                .collect()
+        };
+        let log = read_log("foobar-daemon.log");
+        let error_log = read_log("foobar-daemon.error.log");
+        let audit_log = read_log("foobar-daemon.audit.log");
+
+        assert!(log.contains("informational event"));
+        assert!(log.contains("error event"));
+        assert!(!error_log.contains("informational event"));
+        assert!(error_log.contains("error event"));
+        assert!(log.contains("tester"));
+        assert!(!error_log.contains("tester"));
+        assert!(!audit_log.contains("informational event"));
+        assert!(!audit_log.contains("error event"));
+        assert!(audit_log.contains("tester"));
+    }
+
+    #[test]
+    fn test_runtime_worker_threads() {
+        let runtime = build_runtime(NonZeroUsize::new(3)).unwrap();
+        assert_eq!(runtime.metrics().num_workers(), 3);
+        runtime.block_on(async {
+            assert_eq!(tokio::runtime::Handle::current().metrics().num_workers(), 3);
+        });
+    }
+}
@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write as _};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use nix::errno::Errno;
+use nix::sys::signal::kill;
+use nix::unistd::Pid;
+use tracing::{error, warn};
+
+/// PID file which is removed when dropped
+pub struct PidFile {
+    path: PathBuf,
+}
+
+fn is_process_alive(pid: i32) -> bool {
+    // EPERM means the process exists, but belongs to another user
+    matches!(kill(Pid::from_raw(pid), None), Ok(()) | Err(Errno::EPERM))
+}
+
+fn read_pid(path: &Path) -> anyhow::Result<Option<i32>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("cannot read PID file {}", path.display()))?;
+    Ok(contents.trim().parse().ok().filter(|pid| *pid > 0))
+}
+
+impl PidFile {
+    /// Create PID file with current process PID
+    ///
+    /// Fails if the file already exists and refers to a running
+    /// process. Stale file (referring to a process which no longer
+    /// exists, or with unparsable contents) is replaced.
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let mut stale_removed = false;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(mut file) => {
+                    writeln!(file, "{}", std::process::id())
+                        .with_context(|| format!("cannot write PID file {}", path.display()))?;
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists && !stale_removed => {
+                    if let Some(pid) = read_pid(path)?
+                        && is_process_alive(pid)
+                    {
+                        bail!(
+                            "PID file {} exists and refers to running process {}, is another instance running?",
+                            path.display(),
+                            pid
+                        );
+                    }
+                    warn!(path = %path.display(), "removing stale PID file");
+                    std::fs::remove_file(path).with_context(|| {
+                        format!("cannot remove stale PID file {}", path.display())
+                    })?;
+                    stale_removed = true;
+                }
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("cannot create PID file {}", path.display()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        if let Err(error) = std::fs::remove_file(&self.path) {
+            error!(%error, path = %self.path.display(), "cannot remove PID file");
+        }
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use foobar_common::testing::temp_dir;
+
+    use super::*;
+
+    fn dead_pid() -> i32 {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id() as i32;
+        child.wait().unwrap();
+        pid
+    }
+
+    #[test]
+    fn test_create_and_remove() {
+        let dir = temp_dir();
+        let path = dir.path().join("foobar.pid");
+        let pidfile = PidFile::create(&path).unwrap();
+        assert_eq!(read_pid(&path).unwrap(), Some(std::process::id() as i32));
+        drop(pidfile);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_live_pid() {
+        let dir = temp_dir();
+        let path = dir.path().join("foobar.pid");
+        std::fs::write(&path, format!("{}\n", std::process::id())).unwrap();
+        assert!(PidFile::create(&path).is_err());
+        // file belonging to a running instance must be left intact
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_stale_pid() {
+        let dir = temp_dir();
+        let path = dir.path().join("foobar.pid");
+        std::fs::write(&path, format!("{}\n", dead_pid())).unwrap();
+        let pidfile = PidFile::create(&path).unwrap();
+        assert_eq!(read_pid(&path).unwrap(), Some(std::process::id() as i32));
+        drop(pidfile);
+    }
+
+    #[test]
+    fn test_garbage_pid() {
+        let dir = temp_dir();
+        let path = dir.path().join("foobar.pid");
+        std::fs::write(&path, "garbage").unwrap();
+        let pidfile = PidFile::create(&path).unwrap();
+        assert_eq!(read_pid(&path).unwrap(), Some(std::process::id() as i32));
+        drop(pidfile);
+    }
+}
@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+type Opener<W> = Box<dyn Fn() -> anyhow::Result<W> + Send + Sync>;
+
+struct Inner<W> {
+    open: Opener<W>,
+    writer: Mutex<W>,
+}
+
+/// Log writer which can be reopened, e.g. after external log rotation
+///
+/// Holds a writer produced by the given opener function, and replaces
+/// it with a freshly opened one on `reopen()`. Clones share the same
+/// underlying writer.
+pub struct ReopenableWriter<W>(Arc<Inner<W>>);
+
+impl<W> Clone for ReopenableWriter<W> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<W: Write> ReopenableWriter<W> {
+    pub fn new(
+        open: impl Fn() -> anyhow::Result<W> + Send + Sync + 'static,
+    ) -> anyhow::Result<Self> {
+        let writer = open()?;
+        Ok(Self(Arc::new(Inner {
+            open: Box::new(open),
+            writer: Mutex::new(writer),
+        })))
+    }
+
+    pub fn reopen(&self) -> anyhow::Result<()> {
+        let new_writer = (self.0.open)()?;
+        // writes are done under the same lock, so nothing is lost
+        // between flushing the old writer and replacing it
+        let mut writer = self.lock();
+        writer.flush()?;
+        *writer = new_writer;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, W> {
+        // logging should not stop because of a panic in another thread
+        self.0.writer.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+pub struct ReopenableWriterGuard<'a, W>(MutexGuard<'a, W>);
+
+impl<W: Write> Write for ReopenableWriterGuard<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<'a, W: Write + 'a> MakeWriter<'a> for ReopenableWriter<W> {
+    type Writer = ReopenableWriterGuard<'a, W>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ReopenableWriterGuard(self.lock())
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::fs::File;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use foobar_common::testing::temp_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_reopen_after_rotation() {
+        let dir = temp_dir();
+        let path = dir.path().join("test.log");
+        let rotated_path = dir.path().join("test.log.1");
+
+        let writer = ReopenableWriter::new({
+            let path = path.clone();
+            move || Ok(File::options().create(true).append(true).open(&path)?)
+        })
+        .unwrap();
+
+        writeln!(writer.make_writer(), "first").unwrap();
+        // emulate logrotate which renames the file, then sends SIGHUP
+        std::fs::rename(&path, &rotated_path).unwrap();
+        writeln!(writer.make_writer(), "second").unwrap();
+        writer.reopen().unwrap();
+        writeln!(writer.make_writer(), "third").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&rotated_path).unwrap(),
+            "first\nsecond\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third\n");
+    }
+
+    #[test]
+    fn test_failed_reopen_keeps_writer() {
+        let opened = AtomicBool::new(false);
+        let writer = ReopenableWriter::new(move || {
+            if opened.swap(true, Ordering::Relaxed) {
+                anyhow::bail!("cannot reopen");
+            }
+            Ok(Vec::<u8>::new())
+        })
+        .unwrap();
+
+        writeln!(writer.make_writer(), "first").unwrap();
+        assert!(writer.reopen().is_err());
+        writeln!(writer.make_writer(), "second").unwrap();
+
+        assert_eq!(*writer.lock(), b"first\nsecond\n");
+    }
+}
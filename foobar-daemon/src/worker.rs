@@ -0,0 +1,696 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Local};
+use indoc::indoc;
+use metrics::{counter, gauge, histogram};
+use sqlx::PgPool;
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{error, warn};
+
+use foobar_common::{
+    CronSchedule, LogThrottle, SharedClock, SystemClock, get_setting, set_setting,
+};
+
+/// Background job which is run periodically
+pub trait Worker: Send + Sync + 'static {
+    /// Name used in logs and metric labels
+    fn name(&self) -> &'static str;
+
+    /// Delay between subsequent ticks, unless scheduled
+    fn interval(&self) -> Duration;
+
+    /// Do a single unit of work
+    ///
+    /// Errors are logged and do not stop the worker.
+    fn tick(&self, pool: &PgPool) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// How often paused workers check whether they were resumed
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often pause flag is synchronized with its persisted state
+const PAUSE_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Setting which holds persisted pause state of workers
+const WORKERS_PAUSED_SETTING: &str = "workers_paused";
+
+/// Period during which repeated worker errors are not logged
+const ERROR_LOG_PERIOD: Duration = Duration::from_secs(60);
+
+/// Shared flag which suspends all workers while set
+///
+/// Workers check the flag before each tick, so a tick which is
+/// already running is completed.
+#[derive(Clone, Default)]
+pub struct PauseFlag(Arc<AtomicBool>);
+
+impl PauseFlag {
+    pub fn set_paused(&self, paused: bool) {
+        self.0.store(paused, Ordering::Release);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Set and persist the flag, so it survives restarts and is
+    /// picked up by other daemon instances
+    pub async fn persist(&self, pool: &PgPool, paused: bool) -> Result<(), sqlx::Error> {
+        set_setting(pool, WORKERS_PAUSED_SETTING, &paused).await?;
+        self.set_paused(paused);
+        Ok(())
+    }
+
+    /// Update the flag from persisted state, if there is one
+    pub async fn load(&self, pool: &PgPool) -> anyhow::Result<()> {
+        if let Some(paused) = get_setting(pool, WORKERS_PAUSED_SETTING).await? {
+            self.set_paused(paused);
+        }
+        Ok(())
+    }
+
+    /// Spawn a task keeping the flag in sync with its persisted state
+    ///
+    /// This way, pausing through another daemon instance takes
+    /// effect here too.
+    pub fn spawn_sync(&self, pool: PgPool) -> JoinHandle<()> {
+        let paused = self.clone();
+        tokio::spawn(async move {
+            let mut error_throttle = LogThrottle::new(ERROR_LOG_PERIOD, SystemClock::shared());
+            loop {
+                tokio::time::sleep(PAUSE_SYNC_INTERVAL).await;
+                if let Err(error) = paused.load(&pool).await
+                    && let Some(suppressed) = error_throttle.event()
+                {
+                    warn!(%error, suppressed, "cannot synchronize worker pause state");
+                }
+            }
+        })
+    }
+}
+
+/// Times of last successful tick of each worker
+///
+/// Shared between workers and readiness check, which uses it to
+/// detect workers which are stuck or keep failing. Workers which
+/// have not succeeded yet are counted from the time they were spawned.
+#[derive(Clone)]
+pub struct WorkerHealth {
+    last_success: Arc<Mutex<BTreeMap<&'static str, tokio::time::Instant>>>,
+    clock: SharedClock,
+}
+
+impl WorkerHealth {
+    fn new(clock: SharedClock) -> Self {
+        Self {
+            last_success: Default::default(),
+            clock,
+        }
+    }
+
+    fn record_success(&self, name: &'static str) {
+        self.last_success
+            .lock()
+            .expect("worker health lock should not be poisoned")
+            .insert(name, self.clock.instant());
+    }
+
+    /// Names of workers which have not succeeded within `stale_after`
+    pub fn stale_workers(&self, stale_after: Duration) -> Vec<&'static str> {
+        let now = self.clock.instant();
+        self.last_success
+            .lock()
+            .expect("worker health lock should not be poisoned")
+            .iter()
+            .filter(|(_, last_success)| now.duration_since(**last_success) > stale_after)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
+impl Default for WorkerHealth {
+    fn default() -> Self {
+        Self::new(SystemClock::shared())
+    }
+}
+
+/// Record successful tick, so health checks can detect stuck workers
+async fn record_success(pool: &PgPool, name: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(indoc! {"
+        INSERT INTO worker_runs(worker, last_success)
+        VALUES($1, now())
+        ON CONFLICT(worker) DO UPDATE
+        SET last_success = excluded.last_success
+    "})
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Next scheduled run time, and delay until it from `now`
+///
+/// Never returns the same time twice, even if woken up early.
+fn next_scheduled(
+    schedule: &CronSchedule,
+    last: Option<DateTime<Local>>,
+    now: DateTime<Local>,
+) -> Option<(DateTime<Local>, Duration)> {
+    let after = last.map_or(now, |last| last.max(now));
+    let next = schedule.next_after(&after)?;
+    Some((next, (next - now).to_std().unwrap_or_default()))
+}
+
+async fn run_worker<W: Worker>(
+    worker: Arc<W>,
+    pool: PgPool,
+    paused: PauseFlag,
+    health: WorkerHealth,
+    schedule: Option<CronSchedule>,
+    clock: SharedClock,
+) {
+    let name = worker.name();
+    let mut error_throttle = LogThrottle::new(ERROR_LOG_PERIOD, clock);
+    let mut last_scheduled: Option<DateTime<Local>> = None;
+    loop {
+        if let Some(schedule) = &schedule {
+            let Some((next, delay)) = next_scheduled(schedule, last_scheduled, Local::now()) else {
+                error!(worker = name, %schedule, "schedule has no upcoming times, stopping worker");
+                return;
+            };
+            tokio::time::sleep(delay).await;
+            last_scheduled = Some(next);
+        }
+
+        if paused.is_paused() {
+            gauge!("foobar_worker_paused", "worker" => name).set(1);
+            tokio::time::sleep(PAUSE_CHECK_INTERVAL).await;
+            continue;
+        }
+        gauge!("foobar_worker_paused", "worker" => name).set(0);
+
+        let start = Instant::now();
+        let res = worker.tick(&pool).await;
+        let duration = start.elapsed().as_secs_f64();
+
+        let status = if res.is_ok() { "success" } else { "failure" };
+        counter!("foobar_worker_ticks_total", "worker" => name, "status" => status).increment(1);
+        histogram!("foobar_worker_tick_duration_seconds", "worker" => name).record(duration);
+
+        match res {
+            Ok(()) => {
+                let suppressed = error_throttle.reset();
+                if suppressed > 0 {
+                    warn!(
+                        worker = name,
+                        suppressed, "{suppressed} more errors before recovery"
+                    );
+                }
+                health.record_success(name);
+                gauge!("foobar_worker_last_success_timestamp_seconds", "worker" => name).set(
+                    SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs_f64(),
+                );
+                if let Err(error) = record_success(&pool, name).await {
+                    warn!(worker = name, %error, "cannot record worker run");
+                }
+            }
+            Err(error) => {
+                if let Some(suppressed) = error_throttle.event() {
+                    if suppressed > 0 {
+                        warn!(
+                            worker = name,
+                            suppressed, "{suppressed} more errors in the last minute"
+                        );
+                    }
+                    error!(worker = name, %error, "error in worker");
+                }
+            }
+        }
+
+        if schedule.is_none() {
+            tokio::time::sleep(worker.interval()).await;
+        }
+    }
+}
+
+type SpawnFn = Box<
+    dyn FnOnce(
+            &mut JoinSet<()>,
+            &PgPool,
+            &PauseFlag,
+            &WorkerHealth,
+            usize,
+            Option<CronSchedule>,
+            &SharedClock,
+        ) + Send,
+>;
+
+struct RegisteredWorker {
+    name: &'static str,
+    interval: Duration,
+    spawn: SpawnFn,
+}
+
+/// Set of workers to be run by the daemon
+pub struct WorkerRegistry {
+    workers: Vec<RegisteredWorker>,
+    paused: PauseFlag,
+    health: WorkerHealth,
+    clock: SharedClock,
+}
+
+impl Default for WorkerRegistry {
+    fn default() -> Self {
+        Self {
+            workers: Default::default(),
+            paused: Default::default(),
+            health: Default::default(),
+            clock: SystemClock::shared(),
+        }
+    }
+}
+
+impl WorkerRegistry {
+    /// Use given source of time for workers instead of system clock
+    ///
+    /// Should be called before obtaining [`Self::health`], which
+    /// is tied to the clock.
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.health = WorkerHealth::new(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    pub fn register(mut self, worker: impl Worker) -> Self {
+        let name = worker.name();
+        let interval = worker.interval();
+        let worker = Arc::new(worker);
+        self.workers.push(RegisteredWorker {
+            name,
+            interval,
+            spawn: Box::new(
+                move |tasks, pool, paused, health, concurrency, schedule, clock| {
+                    for _ in 0..concurrency {
+                        tasks.spawn(run_worker(
+                            worker.clone(),
+                            pool.clone(),
+                            paused.clone(),
+                            health.clone(),
+                            schedule.clone(),
+                            clock.clone(),
+                        ));
+                    }
+                },
+            ),
+        });
+        self
+    }
+
+    /// Flag which pauses all workers of this registry
+    pub fn pause_flag(&self) -> PauseFlag {
+        self.paused.clone()
+    }
+
+    /// Last success times of workers of this registry
+    pub fn health(&self) -> WorkerHealth {
+        self.health.clone()
+    }
+
+    /// Names and tick intervals of registered workers
+    pub fn intervals(&self) -> impl Iterator<Item = (&'static str, Duration)> {
+        self.workers
+            .iter()
+            .map(|worker| (worker.name, worker.interval))
+    }
+
+    /// Spawn all registered workers
+    ///
+    /// Each worker is run in the number of concurrent tasks specified
+    /// for it in `concurrency`, one by default. Concurrent instances
+    /// of a worker must coordinate through the database (e.g. by
+    /// claiming rows with `FOR UPDATE SKIP LOCKED`) to avoid
+    /// processing the same data twice.
+    ///
+    /// Workers which have a cron expression in `schedules` tick at
+    /// matching times, others tick at their fixed intervals.
+    ///
+    /// Workers run indefinitely, so tasks only finish if a worker panics.
+    pub fn spawn(
+        self,
+        pool: &PgPool,
+        concurrency: &BTreeMap<String, NonZeroUsize>,
+        schedules: &BTreeMap<String, CronSchedule>,
+    ) -> anyhow::Result<JoinSet<()>> {
+        let is_unknown = |name: &&String| !self.workers.iter().any(|worker| worker.name == *name);
+        if let Some(name) = concurrency.keys().find(is_unknown) {
+            anyhow::bail!("concurrency specified for unknown worker \"{name}\"");
+        }
+        if let Some(name) = schedules.keys().find(is_unknown) {
+            anyhow::bail!("schedule specified for unknown worker \"{name}\"");
+        }
+
+        let mut tasks = JoinSet::new();
+        for worker in self.workers {
+            let concurrency = concurrency.get(worker.name).map_or(1, |n| n.get());
+            // staleness is counted from spawn until the first success
+            self.health.record_success(worker.name);
+            let schedule = schedules.get(worker.name).cloned();
+            (worker.spawn)(
+                &mut tasks,
+                pool,
+                &self.paused,
+                &self.health,
+                concurrency,
+                schedule,
+                &self.clock,
+            );
+        }
+        Ok(tasks)
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use sqlx::postgres::PgPoolOptions;
+
+    use super::*;
+
+    /// Pool which never provides a connection, so recording worker
+    /// runs quickly fails, which does not affect ticking
+    fn unreachable_pool() -> PgPool {
+        PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(10))
+            .connect_lazy("postgresql://localhost:1/nonexistent")
+            .unwrap()
+    }
+
+    struct DummyWorker {
+        name: &'static str,
+        ticks: Arc<AtomicUsize>,
+    }
+
+    impl Worker for DummyWorker {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_millis(10)
+        }
+
+        async fn tick(&self, _: &PgPool) -> anyhow::Result<()> {
+            self.ticks.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_workers_tick() {
+        let pool = unreachable_pool();
+        let foo_ticks = Arc::new(AtomicUsize::new(0));
+        let bar_ticks = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = WorkerRegistry::default()
+            .register(DummyWorker {
+                name: "foo",
+                ticks: foo_ticks.clone(),
+            })
+            .register(DummyWorker {
+                name: "bar",
+                ticks: bar_ticks.clone(),
+            })
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+        assert_eq!(tasks.len(), 2);
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while foo_ticks.load(Ordering::Relaxed) < 3 || bar_ticks.load(Ordering::Relaxed) < 3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("both workers should tick repeatedly");
+
+        tasks.abort_all();
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_worker_run_recorded(pool: PgPool) {
+        let mut tasks = WorkerRegistry::default()
+            .register(DummyWorker {
+                name: "foo",
+                ticks: Default::default(),
+            })
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let recorded: Option<(String,)> =
+                    sqlx::query_as("SELECT worker FROM worker_runs WHERE last_success > now() - interval '1 minute'")
+                        .fetch_optional(&pool)
+                        .await
+                        .unwrap();
+                if recorded.is_some() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("successful tick should be recorded");
+
+        tasks.abort_all();
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_unknown_worker_concurrency(pool: PgPool) {
+        let concurrency = BTreeMap::from([("baz".to_string(), NonZeroUsize::new(2).unwrap())]);
+        assert!(
+            WorkerRegistry::default()
+                .register(DummyWorker {
+                    name: "foo",
+                    ticks: Default::default(),
+                })
+                .spawn(&pool, &concurrency, &Default::default())
+                .is_err()
+        );
+    }
+
+    struct QueueWorker;
+
+    impl Worker for QueueWorker {
+        fn name(&self) -> &'static str {
+            "queue"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        async fn tick(&self, pool: &PgPool) -> anyhow::Result<()> {
+            let mut tx = pool.begin().await?;
+            let id: Option<(i32,)> = sqlx::query_as(indoc! {"
+                SELECT id
+                FROM queue
+                WHERE times_processed = 0
+                ORDER BY id
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            "})
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if let Some((id,)) = id {
+                // widen the window for concurrent instances to pick the same row
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                sqlx::query("UPDATE queue SET times_processed = times_processed + 1 WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            tx.commit().await?;
+            Ok(())
+        }
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_concurrent_workers(pool: PgPool) {
+        sqlx::query("CREATE TABLE queue(id integer PRIMARY KEY, times_processed integer NOT NULL DEFAULT 0)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO queue(id) SELECT generate_series(1, 100)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let concurrency = BTreeMap::from([("queue".to_string(), NonZeroUsize::new(4).unwrap())]);
+        let mut tasks = WorkerRegistry::default()
+            .register(QueueWorker)
+            .spawn(&pool, &concurrency, &Default::default())
+            .unwrap();
+        assert_eq!(tasks.len(), 4);
+
+        tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                let (num_unprocessed,): (i64,) =
+                    sqlx::query_as("SELECT count(*) FROM queue WHERE times_processed = 0")
+                        .fetch_one(&pool)
+                        .await
+                        .unwrap();
+                if num_unprocessed == 0 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("all rows should be processed");
+
+        tasks.abort_all();
+        while tasks.join_next().await.is_some() {}
+
+        let (max_times_processed,): (i32,) =
+            sqlx::query_as("SELECT max(times_processed) FROM queue")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(max_times_processed, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pause() {
+        let pool = unreachable_pool();
+        let ticks = Arc::new(AtomicUsize::new(0));
+
+        let registry = WorkerRegistry::default().register(DummyWorker {
+            name: "foo",
+            ticks: ticks.clone(),
+        });
+        let paused = registry.pause_flag();
+        paused.set_paused(true);
+        let mut tasks = registry
+            .spawn(&pool, &Default::default(), &Default::default())
+            .unwrap();
+
+        // paused worker keeps checking the flag without ticking
+        tokio::time::sleep(PAUSE_CHECK_INTERVAL * 10).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), 0);
+
+        paused.set_paused(false);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while ticks.load(Ordering::Relaxed) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("worker should tick after resume");
+
+        tasks.abort_all();
+    }
+
+    struct TimedWorker {
+        ticks: Arc<Mutex<Vec<tokio::time::Instant>>>,
+    }
+
+    impl Worker for TimedWorker {
+        fn name(&self) -> &'static str {
+            "timed"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_millis(10)
+        }
+
+        async fn tick(&self, _: &PgPool) -> anyhow::Result<()> {
+            self.ticks.lock().unwrap().push(tokio::time::Instant::now());
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduled_worker() {
+        let ticks: Arc<Mutex<Vec<tokio::time::Instant>>> = Default::default();
+        let pool = unreachable_pool();
+        let schedules = BTreeMap::from([("timed".to_string(), "* * * * *".parse().unwrap())]);
+
+        let start = tokio::time::Instant::now();
+        let mut tasks = WorkerRegistry::default()
+            .register(TimedWorker {
+                ticks: ticks.clone(),
+            })
+            .spawn(&pool, &Default::default(), &schedules)
+            .unwrap();
+        while ticks.lock().unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        tasks.abort_all();
+
+        // first tick waits for the start of the next minute instead
+        // of happening right away
+        let delay = ticks.lock().unwrap()[0] - start;
+        assert!(delay > Duration::ZERO && delay <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_next_scheduled() {
+        let schedule: CronSchedule = "*/5 * * * *".parse().unwrap();
+        let time = |time: &str| {
+            DateTime::parse_from_rfc3339(time)
+                .unwrap()
+                .with_timezone(&Local)
+        };
+
+        assert_eq!(
+            next_scheduled(&schedule, None, time("2026-01-01T10:03:30Z")),
+            Some((time("2026-01-01T10:05:00Z"), Duration::from_secs(90)))
+        );
+        // woken up slightly early, same time is not scheduled again
+        assert_eq!(
+            next_scheduled(
+                &schedule,
+                Some(time("2026-01-01T10:05:00Z")),
+                time("2026-01-01T10:04:59Z")
+            ),
+            Some((time("2026-01-01T10:10:00Z"), Duration::from_secs(301)))
+        );
+        // tick took longer than the period, next time is counted from now
+        assert_eq!(
+            next_scheduled(
+                &schedule,
+                Some(time("2026-01-01T10:05:00Z")),
+                time("2026-01-01T10:12:00Z")
+            ),
+            Some((time("2026-01-01T10:15:00Z"), Duration::from_secs(180)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schedule_for_unknown_worker() {
+        let schedules = BTreeMap::from([("nonexistent".to_string(), "* * * * *".parse().unwrap())]);
+        let pool = unreachable_pool();
+        let err = WorkerRegistry::default()
+            .register(TimedWorker {
+                ticks: Default::default(),
+            })
+            .spawn(&pool, &Default::default(), &schedules)
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown worker \"nonexistent\""));
+    }
+}
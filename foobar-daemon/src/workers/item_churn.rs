@@ -0,0 +1,236 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::{Duration, Instant};
+
+use indoc::indoc;
+use metrics::{counter, histogram};
+use sqlx::PgPool;
+
+use crate::worker::Worker;
+
+/// Number of items below which the worker always adds items
+const LOW_WATERMARK: i64 = 10;
+
+/// Number of items above which the worker always removes items
+const HIGH_WATERMARK: i64 = 20;
+
+/// Maximal number of items added in a single tick
+///
+/// Items are added in batches when their number is below half of
+/// the low watermark, so the table is refilled quickly after purge.
+const MAX_BATCH_SIZE: i64 = 8;
+
+/// Example worker which keeps adding and removing items
+pub struct ItemChurnWorker;
+
+/// What a single iteration of the worker has done
+enum Outcome {
+    Insert,
+    Delete,
+    /// Nothing was changed, e.g. because of conflicting inserts
+    Noop,
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Delete => "delete",
+            Self::Noop => "noop",
+        }
+    }
+}
+
+/// Number of items to add in a single tick
+fn batch_size(num_items: i64) -> i64 {
+    if num_items < LOW_WATERMARK / 2 {
+        (LOW_WATERMARK - num_items).min(MAX_BATCH_SIZE)
+    } else {
+        1
+    }
+}
+
+fn item_text(random: f64, index: i64) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::hash::DefaultHasher::new();
+    random.to_bits().hash(&mut hasher);
+    index.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// Insert items in a single query, returning number of actually created ones
+///
+/// Item texts are unique, and items which duplicate existing ones
+/// are skipped and counted in metrics rather than failing the tick.
+async fn insert_items(pool: &PgPool, texts: &[String]) -> Result<u64, sqlx::Error> {
+    let res = sqlx::query(indoc! {"
+        INSERT INTO items(text)
+        SELECT unnest($1::text[])
+        ON CONFLICT(text) DO NOTHING
+    "})
+    .bind(texts)
+    .execute(pool)
+    .await?;
+
+    let created = res.rows_affected();
+    let conflicts = texts.len() as u64 - created;
+    if conflicts > 0 {
+        counter!("foobar_worker_insert_conflicts_total", "worker" => "item_churn")
+            .increment(conflicts);
+    }
+    Ok(created)
+}
+
+/// Add or remove items, depending on their current number
+async fn iterate(pool: &PgPool) -> anyhow::Result<Outcome> {
+    let (num_items, random): (i64, f64) = sqlx::query_as(indoc! {"
+        SELECT
+            count(*), random()
+        FROM items
+    "})
+    .fetch_one(pool)
+    .await?;
+
+    let (changed, outcome) =
+        if num_items < LOW_WATERMARK || (num_items < HIGH_WATERMARK && random < 0.5) {
+            let texts: Vec<String> = (0..batch_size(num_items))
+                .map(|index| item_text(random, index))
+                .collect();
+            (insert_items(pool, &texts).await?, Outcome::Insert)
+        } else {
+            // SKIP LOCKED makes concurrent instances remove different items
+            let res = sqlx::query(indoc! {"
+                DELETE FROM items
+                WHERE
+                    id = (
+                        SELECT id
+                        FROM items
+                        ORDER BY id
+                        LIMIT 1
+                        FOR UPDATE SKIP LOCKED
+                    )
+            "})
+            .execute(pool)
+            .await?;
+            (res.rows_affected(), Outcome::Delete)
+        };
+
+    Ok(if changed > 0 { outcome } else { Outcome::Noop })
+}
+
+impl Worker for ItemChurnWorker {
+    fn name(&self) -> &'static str {
+        "item_churn"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    async fn tick(&self, pool: &PgPool) -> anyhow::Result<()> {
+        // unlike tick duration, this only covers database work
+        let start = Instant::now();
+        let res = iterate(pool).await;
+        let outcome = match &res {
+            Ok(outcome) => outcome.label(),
+            Err(_) => "error",
+        };
+        histogram!(
+            "foobar_worker_iteration_duration_seconds",
+            "worker" => self.name(),
+            "outcome" => outcome
+        )
+        .record(start.elapsed().as_secs_f64());
+        res.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use foobar_common::testing::counter_values;
+
+    use super::*;
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_insert_conflict(pool: PgPool) {
+        let recorder = DebuggingRecorder::new();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let texts = vec!["foo".to_string()];
+        assert_eq!(insert_items(&pool, &texts).await.unwrap(), 1);
+        assert_eq!(insert_items(&pool, &texts).await.unwrap(), 0);
+
+        assert_eq!(count_items(&pool).await, 1);
+
+        assert_eq!(
+            counter_values(&recorder, "foobar_worker_insert_conflicts_total"),
+            vec![1]
+        );
+    }
+
+    async fn count_items(pool: &PgPool) -> i64 {
+        let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        count
+    }
+
+    #[test]
+    fn test_batch_size() {
+        assert_eq!(batch_size(0), MAX_BATCH_SIZE);
+        assert_eq!(batch_size(LOW_WATERMARK / 2 - 1), LOW_WATERMARK / 2 + 1);
+        assert!(batch_size(LOW_WATERMARK / 2 - 1) < MAX_BATCH_SIZE);
+        assert_eq!(batch_size(LOW_WATERMARK / 2), 1);
+        assert_eq!(batch_size(LOW_WATERMARK - 1), 1);
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_batch_insert(pool: PgPool) {
+        ItemChurnWorker.tick(&pool).await.unwrap();
+        assert_eq!(count_items(&pool).await, MAX_BATCH_SIZE);
+
+        ItemChurnWorker.tick(&pool).await.unwrap();
+        assert_eq!(count_items(&pool).await, MAX_BATCH_SIZE + 1);
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_iteration_duration(pool: PgPool) {
+        let recorder = DebuggingRecorder::new();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        for _ in 0..3 {
+            ItemChurnWorker.tick(&pool).await.unwrap();
+        }
+        pool.close().await;
+        assert!(ItemChurnWorker.tick(&pool).await.is_err());
+
+        let mut durations: Vec<(String, usize)> = recorder
+            .snapshotter()
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, ..)| key.key().name() == "foobar_worker_iteration_duration_seconds")
+            .filter_map(|(key, .., value)| match value {
+                DebugValue::Histogram(values) => Some((
+                    key.key()
+                        .labels()
+                        .find(|label| label.key() == "outcome")?
+                        .value()
+                        .to_string(),
+                    values.len(),
+                )),
+                _ => None,
+            })
+            .collect();
+        durations.sort();
+        assert_eq!(
+            durations,
+            vec![("error".to_string(), 1), ("insert".to_string(), 3)]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use foobar_common::testing::temp_dir;
+
+fn read_audit_log(log_directory: &std::path::Path) -> String {
+    std::fs::read_dir(log_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("foobar-daemon.audit.log")
+        })
+        .map(|path| std::fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_pause_audited(_: PgPoolOptions, connect_options: PgConnectOptions) {
+    let dsn = format!(
+        "postgresql://{}@localhost/{}?host={}&port={}",
+        connect_options.get_username(),
+        connect_options.get_database().unwrap(),
+        connect_options.get_host(),
+        connect_options.get_port(),
+    );
+    let admin_listen = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let log_directory = temp_dir();
+
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_foobar-daemon"))
+        .args(["--dsn", &dsn, "--admin-listen", &admin_listen.to_string()])
+        .args(["--admin-token", "secret", "--audit-log", "--log-directory"])
+        .arg(log_directory.path())
+        .spawn()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let mut stream = loop {
+        if let Ok(stream) = TcpStream::connect(admin_listen) {
+            break stream;
+        }
+        if Instant::now() > deadline {
+            daemon.kill().unwrap();
+            panic!("daemon did not start serving admin endpoints");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    write!(
+        stream,
+        "POST /admin/worker/pause HTTP/1.1\r\nhost: localhost\r\nauthorization: Bearer secret\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 "));
+
+    let audit_log = loop {
+        let audit_log = read_audit_log(log_directory.path());
+        if !audit_log.is_empty() || Instant::now() > deadline {
+            break audit_log;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    daemon.kill().unwrap();
+    daemon.wait().unwrap();
+
+    // only audit events get there, with all their fields
+    assert_eq!(audit_log.lines().count(), 1);
+    for field in ["admin_token", "pause_workers", "workers", "success"] {
+        assert!(
+            audit_log.contains(field),
+            "{field} is missing in {audit_log}"
+        );
+    }
+}
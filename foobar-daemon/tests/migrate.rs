@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use sqlx::PgPool;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use foobar_common::{MigrationState, get_migration_status};
+
+#[sqlx::test(migrations = false)]
+async fn test_migrate(_: PgPoolOptions, connect_options: PgConnectOptions) {
+    let dsn = format!(
+        "postgresql://{}@localhost/{}?host={}&port={}",
+        connect_options.get_username(),
+        connect_options.get_database().unwrap(),
+        connect_options.get_host(),
+        connect_options.get_port(),
+    );
+
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_foobar-daemon"))
+        .args(["--dsn", &dsn, "migrate"])
+        .spawn()
+        .unwrap();
+
+    // daemon must exit by itself, as running worker would never finish
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let status = loop {
+        if let Some(status) = daemon.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() > deadline {
+            daemon.kill().unwrap();
+            panic!("daemon did not exit after running migrations");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert!(status.success());
+
+    let pool = PgPool::connect_with(connect_options).await.unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    let status = get_migration_status(&mut conn).await.unwrap();
+    assert!(
+        status
+            .iter()
+            .all(|status| status.state == MigrationState::Applied)
+    );
+
+    // worker would have added some items right away
+    let (num_items,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(num_items, 0);
+}
@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::Path;
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+
+fn git_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn build_time() -> DateTime<Utc> {
+    // support reproducible builds, see https://reproducible-builds.org/docs/source-date-epoch/
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .and_then(|epoch| DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(Utc::now)
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src");
+    for path in ["../.git/HEAD", "../.git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+
+    println!(
+        "cargo:rustc-env=FOOBAR_GIT_HASH={}",
+        git_hash().as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "cargo:rustc-env=FOOBAR_BUILD_TIME={}",
+        build_time().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+}
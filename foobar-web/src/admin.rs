@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+
+use foobar_common::{check_api_key, check_bearer_token, hash_api_key};
+
+use crate::state::AppState;
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Audit actor for requests authorized with the admin token
+const ADMIN_TOKEN_ACTOR: &str = "admin_token";
+
+/// Extractor which only succeeds for requests authorized for administrative access
+///
+/// Requests must carry either `Authorization: Bearer <token>` header
+/// with the configured admin token, or one of configured API keys,
+/// passed either as bearer token or in `X-API-Key` header. When
+/// neither admin token nor API keys are configured, administrative
+/// endpoints are disabled and respond with 404.
+pub struct AdminAuth {
+    /// Who performed the request, for the audit log
+    ///
+    /// API keys are identified by a prefix of their hash, as listed
+    /// in configuration, so the key itself is not revealed.
+    pub actor: String,
+}
+
+impl FromRequestParts<Arc<AppState>> for AdminAuth {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let config = &state.config;
+        if config.admin_token.is_none() && config.api_key_hashes.is_empty() {
+            return Err(StatusCode::NOT_FOUND.into_response());
+        }
+
+        let header = |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let authorization = header(header::AUTHORIZATION.as_str());
+        let api_key = header(API_KEY_HEADER)
+            .or_else(|| authorization.and_then(|value| value.strip_prefix("Bearer ")));
+
+        let token_valid = config
+            .admin_token
+            .as_deref()
+            .is_some_and(|admin_token| check_bearer_token(authorization, admin_token));
+        let api_key_valid =
+            api_key.is_some_and(|api_key| check_api_key(api_key, &config.api_key_hashes));
+
+        if token_valid {
+            Ok(AdminAuth {
+                actor: ADMIN_TOKEN_ACTOR.to_string(),
+            })
+        } else if let Some(api_key) = api_key.filter(|_| api_key_valid) {
+            Ok(AdminAuth {
+                actor: format!("api_key:{}", &hash_api_key(api_key)[..8]),
+            })
+        } else {
+            Err((
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+            )
+                .into_response())
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_myroutes::{PathBuilder, PathBuilderError};
+use url::Url;
+
+/// Externally visible URL of the site, for constructing absolute URLs
+///
+/// Site may be served under a subpath (e.g. `https://example.com/foobar/`),
+/// in which case route paths are appended to it. Path of the stored URL
+/// always ends with a slash, so joining works the same regardless of
+/// whether it was configured with one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BaseUrl(Url);
+
+impl BaseUrl {
+    pub fn new(mut url: Url) -> anyhow::Result<Self> {
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("base URL {url} must use http or https scheme");
+        }
+        if url.host().is_none() {
+            anyhow::bail!("base URL {url} must have a host");
+        }
+        if url.query().is_some() || url.fragment().is_some() {
+            anyhow::bail!("base URL {url} must not have query or fragment");
+        }
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        Ok(Self(url))
+    }
+
+    pub fn as_url(&self) -> &Url {
+        &self.0
+    }
+
+    /// Construct absolute URL for site local path
+    ///
+    /// Path is always treated as relative to the base URL, regardless
+    /// of whether it starts with a slash, and may contain query string.
+    pub fn absolute_url(&self, path: &str) -> Url {
+        // explicit ./ prevents path from being interpreted as URL with scheme
+        self.0
+            .join(&format!("./{}", path.trim_start_matches('/')))
+            .expect("relative path should be joinable to base URL")
+    }
+
+    /// Construct absolute URL for a route
+    pub fn url_for_absolute(&self, path: &PathBuilder) -> Result<Url, PathBuilderError> {
+        Ok(self.absolute_url(&path.build()?))
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use crate::routes::Route;
+
+    use super::*;
+
+    fn base_url(url: &str) -> BaseUrl {
+        BaseUrl::new(Url::parse(url).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_absolute_url() {
+        for base in ["https://example.com", "https://example.com/"] {
+            let base = base_url(base);
+            assert_eq!(base.as_url().as_str(), "https://example.com/");
+            assert_eq!(base.absolute_url("/").as_str(), "https://example.com/");
+            assert_eq!(
+                base.absolute_url("/item/1").as_str(),
+                "https://example.com/item/1"
+            );
+            assert_eq!(
+                base.absolute_url("item/1?after=foo").as_str(),
+                "https://example.com/item/1?after=foo"
+            );
+        }
+    }
+
+    #[test]
+    fn test_absolute_url_subpath() {
+        for base in [
+            "https://example.com/foo/bar",
+            "https://example.com/foo/bar/",
+        ] {
+            let base = base_url(base);
+            assert_eq!(
+                base.absolute_url("/").as_str(),
+                "https://example.com/foo/bar/"
+            );
+            assert_eq!(
+                base.absolute_url("/item/1").as_str(),
+                "https://example.com/foo/bar/item/1"
+            );
+            assert_eq!(
+                base.absolute_url("javascript:alert(1)").as_str(),
+                "https://example.com/foo/bar/javascript:alert(1)"
+            );
+        }
+    }
+
+    #[test]
+    fn test_url_for_absolute() {
+        let base = base_url("https://example.com/foo");
+        assert_eq!(
+            base.url_for_absolute(&Route::Item.url_for().path_param("id", 1).unwrap())
+                .unwrap()
+                .as_str(),
+            "https://example.com/foo/item/1"
+        );
+        assert!(base.url_for_absolute(&Route::Item.url_for()).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        for url in [
+            "ftp://example.com/",
+            "unix:/run/foobar.sock",
+            "https://example.com/?foo=bar",
+            "https://example.com/#foo",
+        ] {
+            assert!(BaseUrl::new(Url::parse(url).unwrap()).is_err(), "{url}");
+        }
+    }
+}
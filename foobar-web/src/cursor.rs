@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Opaque cursors for keyset pagination
+//!
+//! Cursor carries `(time, id)` of the last item on the page, so the
+//! next page can be fetched with `WHERE (time, id) > ($1, $2)`, which
+//! is served by range scan on `items(time, id)` index regardless of
+//! how deep the page is.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub time: DateTime<Utc>,
+    pub id: i32,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", self.time.timestamp_micros(), self.id))
+    }
+
+    /// Decode cursor, returning `None` if it's malformed
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let decoded = std::str::from_utf8(&decoded).ok()?;
+        let (time, id) = decoded.split_once(':')?;
+        Some(Self {
+            time: DateTime::from_timestamp_micros(time.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let cursor = Cursor {
+            time: DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+            id: 42,
+        };
+        let encoded = cursor.encode();
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_eq!(Cursor::decode(&encoded), Some(cursor));
+
+        let cursor = Cursor {
+            time: DateTime::UNIX_EPOCH,
+            id: -1,
+        };
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let encode = |s: &str| URL_SAFE_NO_PAD.encode(s);
+        assert_eq!(Cursor::decode(""), None);
+        assert_eq!(Cursor::decode("!!!"), None);
+        assert_eq!(Cursor::decode(&encode("garbage")), None);
+        assert_eq!(Cursor::decode(&encode("1:")), None);
+        assert_eq!(Cursor::decode(&encode(":1")), None);
+        assert_eq!(Cursor::decode(&encode("1:2:3")), None);
+        assert_eq!(Cursor::decode(&encode("1:99999999999")), None);
+        assert_eq!(Cursor::decode(&encode(&format!("{}:1", i64::MAX))), None);
+        assert_eq!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode([0xff, b':', b'1'])),
+            None
+        );
+    }
+}
@@ -0,0 +1,275 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Feature flags
+//!
+//! Flags are kept in the settings store, so they can be toggled at
+//! runtime without restart, and are checked on each request through
+//! the settings cache, so changes take effect within its TTL. A flag is
+//! either on, off, or rolled out to a percentage of clients, in which
+//! case clients are bucketed by a random id stored in their session,
+//! so each client consistently sees the same variant.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use tracing::warn;
+
+use crate::session::Session;
+use crate::state::AppState;
+
+/// Session value holding client id used for percentage rollouts
+const BUCKET_SESSION_KEY: &str = "bucket";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    AboutPage,
+}
+
+impl Feature {
+    pub const ALL: &[Feature] = &[Feature::AboutPage];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::AboutPage => "about_page",
+        }
+    }
+
+    /// State of the flag when it was never set
+    fn default_state(self) -> FlagState {
+        match self {
+            Feature::AboutPage => FlagState::On,
+        }
+    }
+
+    fn setting_key(self) -> String {
+        format!("feature.{}", self.name())
+    }
+}
+
+impl FromStr for Feature {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Feature::ALL
+            .iter()
+            .copied()
+            .find(|feature| feature.name() == s)
+            .ok_or_else(|| anyhow::anyhow!("unknown feature \"{s}\""))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagState {
+    Off,
+    On,
+    /// Enabled for given percentage of clients
+    Percentage(u8),
+}
+
+impl FlagState {
+    fn is_enabled_for(self, feature: Feature, bucket: Option<&str>) -> bool {
+        match self {
+            FlagState::Off => false,
+            FlagState::On => true,
+            FlagState::Percentage(percentage) => bucket
+                .is_some_and(|bucket| rollout_position(feature, bucket) < u64::from(percentage)),
+        }
+    }
+}
+
+impl FromStr for FlagState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "on" => Ok(FlagState::On),
+            "off" => Ok(FlagState::Off),
+            _ => s
+                .strip_suffix('%')
+                .and_then(|percentage| percentage.parse().ok())
+                .filter(|percentage| *percentage <= 100)
+                .map(FlagState::Percentage)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("invalid flag state \"{s}\", expected on, off or percentage")
+                }),
+        }
+    }
+}
+
+impl fmt::Display for FlagState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagState::Off => write!(f, "off"),
+            FlagState::On => write!(f, "on"),
+            FlagState::Percentage(percentage) => write!(f, "{percentage}%"),
+        }
+    }
+}
+
+/// Stable position of a client in `[0, 100)` range for given feature
+///
+/// Feature name is mixed in, so different features are rolled out
+/// to different subsets of clients.
+fn rollout_position(feature: Feature, bucket: &str) -> u64 {
+    cityhasher::hash::<u64>(format!("{}:{bucket}", feature.name())) % 100
+}
+
+/// Read state of a feature flag
+pub async fn get_flag(state: &AppState, feature: Feature) -> anyhow::Result<FlagState> {
+    Ok(state
+        .settings
+        .get(&*state.clock, &state.pool, &feature.setting_key())
+        .await?
+        .unwrap_or_else(|| feature.default_state()))
+}
+
+/// Change state of a feature flag
+pub async fn set_flag(
+    state: &AppState,
+    feature: Feature,
+    flag: FlagState,
+) -> Result<(), sqlx::Error> {
+    state
+        .settings
+        .set(
+            &*state.clock,
+            &state.admin_pool,
+            &feature.setting_key(),
+            &flag,
+        )
+        .await
+}
+
+/// Get client id for percentage rollouts, assigning new one if needed
+fn session_bucket(session: &mut Session) -> String {
+    if let Some(bucket) = session.get(BUCKET_SESSION_KEY) {
+        return bucket.to_string();
+    }
+    let bucket = format!(
+        "{:016x}",
+        getrandom::u64().expect("system random source should be available")
+    );
+    session.set(BUCKET_SESSION_KEY, bucket.clone());
+    bucket
+}
+
+/// Extractor for the set of features enabled for current request
+///
+/// If flags cannot be read, defaults are used so pages are still
+/// served while the database is having problems.
+pub struct Features {
+    enabled: Vec<Feature>,
+}
+
+impl Features {
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.enabled.contains(&feature)
+    }
+}
+
+impl FromRequestParts<Arc<AppState>> for Features {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let mut flags = Vec::with_capacity(Feature::ALL.len());
+        for &feature in Feature::ALL {
+            let flag = get_flag(state, feature).await.unwrap_or_else(|err| {
+                warn!(
+                    feature = feature.name(),
+                    "cannot read feature flag, using default: {err:#}"
+                );
+                feature.default_state()
+            });
+            flags.push((feature, flag));
+        }
+
+        // only touch the session when needed, so it's not created for
+        // every visitor while no rollout is in progress
+        let bucket = if flags
+            .iter()
+            .any(|(_, flag)| matches!(flag, FlagState::Percentage(_)))
+        {
+            let mut session = Session::from_request_parts(parts, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            Some(session_bucket(&mut session))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            enabled: flags
+                .into_iter()
+                .filter(|(feature, flag)| flag.is_enabled_for(*feature, bucket.as_deref()))
+                .map(|(feature, _)| feature)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_state_parse() {
+        assert_eq!("on".parse::<FlagState>().unwrap(), FlagState::On);
+        assert_eq!("off".parse::<FlagState>().unwrap(), FlagState::Off);
+        assert_eq!("0%".parse::<FlagState>().unwrap(), FlagState::Percentage(0));
+        assert_eq!(
+            "100%".parse::<FlagState>().unwrap(),
+            FlagState::Percentage(100)
+        );
+        assert!("101%".parse::<FlagState>().is_err());
+        assert!("-1%".parse::<FlagState>().is_err());
+        assert!("50".parse::<FlagState>().is_err());
+        assert!("yes".parse::<FlagState>().is_err());
+    }
+
+    #[test]
+    fn test_flag_state_roundtrip() {
+        for state in [FlagState::On, FlagState::Off, FlagState::Percentage(42)] {
+            assert_eq!(state.to_string().parse::<FlagState>().unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn test_feature_parse() {
+        assert_eq!("about_page".parse::<Feature>().unwrap(), Feature::AboutPage);
+        assert!("unknown".parse::<Feature>().is_err());
+    }
+
+    #[test]
+    fn test_percentage_rollout() {
+        let buckets: Vec<String> = (0..1000).map(|i| format!("{i:016x}")).collect();
+        let enabled_count = |state: FlagState| {
+            buckets
+                .iter()
+                .filter(|bucket| state.is_enabled_for(Feature::AboutPage, Some(bucket)))
+                .count()
+        };
+
+        assert_eq!(enabled_count(FlagState::Percentage(0)), 0);
+        assert_eq!(enabled_count(FlagState::Percentage(100)), buckets.len());
+        assert!((400..600).contains(&enabled_count(FlagState::Percentage(50))));
+
+        // growing rollout only adds clients
+        for bucket in &buckets {
+            if FlagState::Percentage(20).is_enabled_for(Feature::AboutPage, Some(bucket)) {
+                assert!(FlagState::Percentage(30).is_enabled_for(Feature::AboutPage, Some(bucket)));
+            }
+        }
+
+        // clients without session are not included
+        assert!(!FlagState::Percentage(100).is_enabled_for(Feature::AboutPage, None));
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::{Arc, LazyLock};
+
+use async_graphql::connection::{Connection, CursorType, Edge};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+use axum::Json;
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use indoc::indoc;
+use sqlx::FromRow;
+
+use crate::cursor::Cursor;
+use crate::state::AppState;
+
+/// Maximum number of items returned by single `items` query, also
+/// used when `first` is not specified
+const MAX_ITEMS_PER_PAGE: i32 = 100;
+
+/// Limits of query depth and complexity, so a single request cannot
+/// make the server do arbitrary amount of work
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 1000;
+
+type FoobarSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+static GRAPHQL_SCHEMA: LazyLock<FoobarSchema> = LazyLock::new(|| {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+});
+
+impl CursorType for Cursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        Cursor::decode(s).ok_or("invalid cursor")
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.encode()
+    }
+}
+
+#[derive(FromRow, SimpleObject)]
+struct Item {
+    id: i32,
+    text: String,
+    time: DateTime<Utc>,
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Items in order of creation, paginated with cursor of the last
+    /// item of the previous page
+    #[graphql(
+        complexity = "first.map_or(MAX_ITEMS_PER_PAGE, |first| first.clamp(0, MAX_ITEMS_PER_PAGE)) as usize * child_complexity"
+    )]
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<Cursor, Item>> {
+        let limit = match first {
+            None => MAX_ITEMS_PER_PAGE,
+            Some(first) if (0..=MAX_ITEMS_PER_PAGE).contains(&first) => first,
+            Some(_) => {
+                return Err(format!("first must be between 0 and {MAX_ITEMS_PER_PAGE}").into());
+            }
+        };
+        let after = after
+            .map(|after| Cursor::decode_cursor(&after))
+            .transpose()?;
+
+        let state = ctx.data::<Arc<AppState>>()?;
+        let mut tx = state.read_tx().await?;
+
+        let mut items: Vec<Item> = match after {
+            None => sqlx::query_as(indoc! {r#"
+                SELECT
+                    id,
+                    text,
+                    time
+                FROM items
+                ORDER BY time, id
+                LIMIT $1
+            "#}),
+            Some(cursor) => sqlx::query_as(indoc! {r#"
+                SELECT
+                    id,
+                    text,
+                    time
+                FROM items
+                WHERE (time, id) > ($1, $2)
+                ORDER BY time, id
+                LIMIT $3
+            "#})
+            .bind(cursor.time)
+            .bind(cursor.id),
+        }
+        .bind(i64::from(limit) + 1)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let has_next_page = items.len() > limit as usize;
+        items.truncate(limit as usize);
+
+        let mut connection = Connection::new(after.is_some(), has_next_page);
+        connection.edges.extend(items.into_iter().map(|item| {
+            Edge::new(
+                Cursor {
+                    time: item.time,
+                    id: item.id,
+                },
+                item,
+            )
+        }));
+        Ok(connection)
+    }
+
+    async fn item(&self, ctx: &Context<'_>, id: i32) -> async_graphql::Result<Option<Item>> {
+        let state = ctx.data::<Arc<AppState>>()?;
+        let mut tx = state.read_tx().await?;
+
+        Ok(sqlx::query_as(indoc! {r#"
+            SELECT
+                id,
+                text,
+                time
+            FROM items
+            WHERE id = $1
+        "#})
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?)
+    }
+}
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn graphql(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(GRAPHQL_SCHEMA.execute(request.data(state)).await)
+}
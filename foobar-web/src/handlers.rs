@@ -2,11 +2,23 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 mod about;
+mod admin_features;
+mod admin_migrations;
+mod api_docs;
+mod health;
 mod index;
 mod item;
+mod openapi;
 mod static_files;
+mod version;
 
 pub use about::*;
+pub use admin_features::*;
+pub use admin_migrations::*;
+pub use api_docs::*;
+pub use health::*;
 pub use index::*;
 pub use item::*;
+pub use openapi::*;
 pub use static_files::*;
+pub use version::*;
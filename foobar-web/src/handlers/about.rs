@@ -1,25 +1,43 @@
 // SPDX-FileCopyrightText: Copyright 2025 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::sync::Arc;
+
 use askama::Template;
-use axum::response::{Html, IntoResponse};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
 
+use crate::features::{Feature, Features};
 use crate::result::HandlerResult;
 use crate::routes::MyRoute;
+use crate::state::AppState;
+use crate::template::{TemplateContext, render_html};
 
 #[derive(Template)]
 #[template(path = "about.html")]
 struct TemplateParams<'a> {
+    ctx: &'a TemplateContext,
     my_route: &'a MyRoute,
+    features: &'a Features,
 }
 
 #[cfg_attr(not(coverage), tracing::instrument(skip_all))]
-pub async fn about(my_route: MyRoute) -> HandlerResult {
-    Ok(Html(
-        TemplateParams {
+pub async fn about(
+    my_route: MyRoute,
+    features: Features,
+    State(state): State<Arc<AppState>>,
+) -> HandlerResult {
+    if !features.is_enabled(Feature::AboutPage) {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    render_html(
+        "about.html",
+        &TemplateParams {
+            ctx: &state.template_context,
             my_route: &my_route,
-        }
-        .render()?,
+            features: &features,
+        },
     )
-    .into_response())
 }
@@ -0,0 +1,44 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use tracing::info;
+
+use foobar_common::audit;
+
+use crate::admin::AdminAuth;
+use crate::features::{Feature, FlagState, set_flag};
+use crate::result::HandlerResult;
+use crate::state::AppState;
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn admin_feature(
+    auth: AdminAuth,
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    body: String,
+) -> HandlerResult {
+    let Ok(feature) = name.parse::<Feature>() else {
+        return Ok((StatusCode::NOT_FOUND, "Unknown feature").into_response());
+    };
+    let flag = match body.trim().parse::<FlagState>() {
+        Ok(flag) => flag,
+        Err(err) => return Ok((StatusCode::BAD_REQUEST, err.to_string()).into_response()),
+    };
+
+    let result = set_flag(&state, feature, flag).await;
+    audit!(
+        actor = auth.actor,
+        action = "set_feature_flag",
+        target = feature.name(),
+        result = if result.is_ok() { "success" } else { "failure" },
+    );
+    result?;
+    info!(feature = feature.name(), state = %flag, "feature flag changed");
+
+    Ok(flag.to_string().into_response())
+}
@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use serde::Serialize;
+
+use foobar_common::{MigrationStatus, get_migration_status};
+
+use crate::admin::AdminAuth;
+use crate::result::HandlerResult;
+use crate::state::AppState;
+
+#[derive(Serialize)]
+struct Migration {
+    version: i64,
+    description: String,
+    checksum: String,
+    state: &'static str,
+}
+
+impl From<MigrationStatus> for Migration {
+    fn from(status: MigrationStatus) -> Self {
+        Self {
+            version: status.version,
+            description: status.description,
+            checksum: status.checksum.iter().fold(String::new(), |mut res, byte| {
+                let _ = write!(res, "{byte:02x}");
+                res
+            }),
+            state: status.state.as_str(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Response {
+    migrations: Vec<Migration>,
+    up_to_date: bool,
+}
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn admin_migrations(_: AdminAuth, State(state): State<Arc<AppState>>) -> HandlerResult {
+    let mut tx = state.admin_read_tx().await?;
+
+    let status = get_migration_status(&mut tx).await?;
+
+    Ok(Json(Response {
+        up_to_date: !status.iter().any(MigrationStatus::is_problem),
+        migrations: status.into_iter().map(Migration::from).collect(),
+    })
+    .into_response())
+}
@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+
+use crate::features::Features;
+use crate::openapi::operation;
+use crate::result::HandlerResult;
+use crate::routes::{MyRoute, Route};
+use crate::state::AppState;
+use crate::template::{TemplateContext, render_html};
+
+struct Endpoint {
+    method: &'static str,
+    path: &'static str,
+    summary: &'static str,
+    admin: bool,
+}
+
+#[derive(Template)]
+#[template(path = "api_docs.html")]
+struct TemplateParams<'a> {
+    ctx: &'a TemplateContext,
+    my_route: &'a MyRoute,
+    features: &'a Features,
+    endpoints: Vec<Endpoint>,
+}
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn api_docs(
+    my_route: MyRoute,
+    features: Features,
+    State(state): State<Arc<AppState>>,
+) -> HandlerResult {
+    if !state.config.api_docs {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let endpoints = Route::ALL
+        .iter()
+        .filter_map(|route| {
+            operation(*route).map(|operation| Endpoint {
+                method: operation.method,
+                path: route.path(),
+                summary: operation.summary,
+                admin: operation.admin,
+            })
+        })
+        .collect();
+
+    render_html(
+        "api_docs.html",
+        &TemplateParams {
+            ctx: &state.template_context,
+            my_route: &my_route,
+            features: &features,
+            endpoints,
+        },
+    )
+}
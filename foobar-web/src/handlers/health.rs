@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::result::HandlerResult;
+use crate::state::AppState;
+
+/// Default age of last successful worker run after which it's considered stale
+pub const DEFAULT_WORKER_STALE_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Fail,
+}
+
+#[derive(Serialize)]
+struct Check {
+    status: Status,
+    latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl Check {
+    fn new(started: Instant, result: Result<(), String>) -> Self {
+        let (status, detail) = match result {
+            Ok(()) => (Status::Ok, None),
+            Err(detail) => (Status::Fail, Some(detail)),
+        };
+        Self {
+            status,
+            latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+            detail,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Checks {
+    database: Check,
+    worker_last_run: Check,
+}
+
+#[derive(Serialize)]
+struct Response {
+    status: Status,
+    checks: Checks,
+}
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn health() -> HandlerResult {
+    Ok("OK".into_response())
+}
+
+async fn check_database(state: &AppState) -> Check {
+    let started = Instant::now();
+    let result = sqlx::query("SELECT 1").execute(&state.pool).await;
+    Check::new(
+        started,
+        result.map(|_| ()).map_err(|err| {
+            warn!(%err, "database health check failed");
+            "database is not available".to_string()
+        }),
+    )
+}
+
+async fn check_worker_last_run(state: &AppState) -> Check {
+    let started = Instant::now();
+    let stale_after = state
+        .config
+        .worker_stale_after
+        .unwrap_or(DEFAULT_WORKER_STALE_AFTER);
+    let result: Result<Option<DateTime<Utc>>, _> =
+        sqlx::query_scalar("SELECT max(last_success) FROM worker_runs")
+            .fetch_one(&state.pool)
+            .await;
+    let result = match result {
+        Ok(Some(last_success)) => {
+            let age = (state.clock.now() - last_success)
+                .to_std()
+                .unwrap_or_default();
+            if age <= stale_after {
+                Ok(())
+            } else {
+                Err(format!("last successful run {}s ago", age.as_secs()))
+            }
+        }
+        Ok(None) => Err("no successful runs recorded".to_string()),
+        Err(err) => {
+            warn!(%err, "worker health check failed");
+            Err("cannot fetch worker runs".to_string())
+        }
+    };
+    Check::new(started, result)
+}
+
+/// Health check which reports status of each dependency
+///
+/// Unlike [`health`], which is a cheap liveness check, this
+/// queries the database.
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn health_detailed(State(state): State<Arc<AppState>>) -> HandlerResult {
+    let checks = Checks {
+        database: check_database(&state).await,
+        worker_last_run: check_worker_last_run(&state).await,
+    };
+    let status = if [&checks.database, &checks.worker_last_run]
+        .iter()
+        .all(|check| check.status == Status::Ok)
+    {
+        Status::Ok
+    } else {
+        Status::Fail
+    };
+    let code = match status {
+        Status::Ok => StatusCode::OK,
+        Status::Fail => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    Ok((code, Json(Response { status, checks })).into_response())
+}
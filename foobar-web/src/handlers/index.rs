@@ -5,14 +5,31 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::extract::State;
-use axum::response::{Html, IntoResponse};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
 use chrono::{DateTime, Utc};
 use indoc::indoc;
+use serde::Deserialize;
 use sqlx::FromRow;
 
+use foobar_common::retry_transient;
+
+use crate::cursor::Cursor;
+use crate::features::Features;
+use crate::query::LimitedQuery;
 use crate::result::HandlerResult;
 use crate::routes::MyRoute;
+use crate::server_timing;
 use crate::state::AppState;
+use crate::template::{TemplateContext, render_html_sized};
+
+/// Number of items shown on a single page
+const ITEMS_PER_PAGE: usize = 100;
+
+#[derive(Deserialize)]
+pub struct QueryParams {
+    after: Option<String>,
+}
 
 #[derive(FromRow)]
 struct Item {
@@ -23,30 +40,89 @@ struct Item {
 
 #[derive(Template)]
 #[template(path = "index.html")]
-struct TemplateParams<'a> {
-    my_route: &'a MyRoute,
-    items: &'a [Item],
+struct TemplateParams {
+    ctx: Arc<TemplateContext>,
+    my_route: MyRoute,
+    features: Features,
+    items: Vec<Item>,
+    next_cursor: Option<String>,
 }
 
 #[cfg_attr(not(coverage), tracing::instrument(skip_all))]
-pub async fn index(my_route: MyRoute, State(state): State<Arc<AppState>>) -> HandlerResult {
-    let items: Vec<Item> = sqlx::query_as(indoc! {r#"
-        SELECT
-            id,
-            text,
-            time
-        FROM items
-        ORDER BY time, id
-    "#})
-    .fetch_all(&state.pool)
+pub async fn index(
+    my_route: MyRoute,
+    features: Features,
+    LimitedQuery(query): LimitedQuery<QueryParams>,
+    State(state): State<Arc<AppState>>,
+) -> HandlerResult {
+    let after = match query.after.as_deref().map(Cursor::decode) {
+        None => None,
+        Some(Some(cursor)) => Some(cursor),
+        Some(None) => return Ok((StatusCode::BAD_REQUEST, "Invalid cursor").into_response()),
+    };
+
+    // fetch one extra item to know whether there's a next page
+    let mut items: Vec<Item> = server_timing::measure(
+        "db",
+        retry_transient(&*state.clock, state.config.db_retries, || async {
+            let mut tx = state.read_tx().await?;
+            // separate queries instead of `$1 IS NULL OR ...`, so the
+            // planner may use index range scan even with generic plans
+            let query = match after {
+                None => sqlx::query_as(indoc! {r#"
+                    SELECT
+                        id,
+                        text,
+                        time
+                    FROM items
+                    ORDER BY time, id
+                    LIMIT $1
+                "#}),
+                Some(cursor) => sqlx::query_as(indoc! {r#"
+                    SELECT
+                        id,
+                        text,
+                        time
+                    FROM items
+                    WHERE (time, id) > ($1, $2)
+                    ORDER BY time, id
+                    LIMIT $3
+                "#})
+                .bind(cursor.time)
+                .bind(cursor.id),
+            };
+            query
+                .bind(ITEMS_PER_PAGE as i64 + 1)
+                .fetch_all(&mut *tx)
+                .await
+        }),
+    )
     .await?;
 
-    Ok(Html(
+    let next_cursor = if items.len() > ITEMS_PER_PAGE {
+        items.truncate(ITEMS_PER_PAGE);
+        items.last().map(|item| {
+            Cursor {
+                time: item.time,
+                id: item.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    let size = items.len();
+    render_html_sized(
+        "index.html",
         TemplateParams {
-            my_route: &my_route,
-            items: &items,
-        }
-        .render()?,
+            ctx: state.template_context.clone(),
+            my_route,
+            features,
+            items,
+            next_cursor,
+        },
+        size,
     )
-    .into_response())
+    .await
 }
@@ -6,57 +6,77 @@ use std::sync::Arc;
 use askama::Template;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::response::{Html, IntoResponse};
+use axum::response::IntoResponse;
 use chrono::{DateTime, Utc};
 use indoc::indoc;
 use sqlx::FromRow;
 
+use foobar_common::retry_transient;
+
+use crate::features::Features;
 use crate::result::HandlerResult;
 use crate::routes::MyRoute;
+use crate::server_timing;
 use crate::state::AppState;
+use crate::template::{TemplateContext, render_html};
 
 #[derive(FromRow)]
 struct Item {
     id: i32,
     text: String,
     time: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 #[derive(Template)]
 #[template(path = "item.html")]
 struct TemplateParams<'a> {
+    ctx: &'a TemplateContext,
     my_route: &'a MyRoute,
+    features: &'a Features,
     item: &'a Item,
 }
 
 #[cfg_attr(not(coverage), tracing::instrument(skip_all))]
 pub async fn item(
     my_route: MyRoute,
+    features: Features,
     Path(id): Path<u64>,
     State(state): State<Arc<AppState>>,
 ) -> HandlerResult {
-    let item: Option<Item> = sqlx::query_as(indoc! {r#"
-        SELECT
-            id,
-            text,
-            time
-        FROM items
-        WHERE id = $1
-    "#})
-    .bind(id as i64)
-    .fetch_optional(&state.pool)
+    let item: Option<Item> = server_timing::measure(
+        "db",
+        retry_transient(&*state.clock, state.config.db_retries, || async {
+            let mut tx = state.read_tx().await?;
+            sqlx::query_as(indoc! {r#"
+                SELECT
+                    id,
+                    text,
+                    time,
+                    created_at,
+                    updated_at
+                FROM items
+                WHERE id = $1
+            "#})
+            .bind(id as i64)
+            .fetch_optional(&mut *tx)
+            .await
+        }),
+    )
     .await?;
 
     let Some(item) = item else {
         return Ok((StatusCode::NOT_FOUND, "Item not found").into_response());
     };
 
-    Ok(Html(
-        TemplateParams {
+    render_html(
+        "item.html",
+        &TemplateParams {
+            ctx: &state.template_context,
             my_route: &my_route,
+            features: &features,
             item: &item,
-        }
-        .render()?,
+        },
     )
-    .into_response())
 }
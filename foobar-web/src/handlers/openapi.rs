@@ -0,0 +1,13 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::Json;
+use axum::response::IntoResponse;
+
+use crate::openapi::OPENAPI_SPEC;
+use crate::result::HandlerResult;
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn openapi() -> HandlerResult {
+    Ok(Json(&*OPENAPI_SPEC).into_response())
+}
@@ -4,9 +4,17 @@
 use axum::extract::Path;
 use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::response::IntoResponse;
+use tracing::debug;
 
 use crate::result::HandlerResult;
-use crate::static_files::STATIC_FILES;
+use crate::static_files::{STATIC_FILES, is_safe_file_name};
+use crate::vary::add_vary;
+
+/// Policy for content types which may run scripts when opened directly
+///
+/// Static files are not expected to contain any scripts, so these are
+/// disabled completely by sandboxing.
+const ACTIVE_CONTENT_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; sandbox";
 
 enum HttpCacheMode {
     ShortLived,
@@ -24,13 +32,28 @@ impl HttpCacheMode {
     }
 }
 
+/// Serve embedded static file by its original or hashed name
+///
+/// Names are matched case sensitively, so e.g. `Style.css` does not
+/// refer to `style.css`; this avoids ambiguity between files which
+/// differ only in case.
 pub fn static_file_generic(file_name: &str, headers: HeaderMap) -> HandlerResult {
+    if !is_safe_file_name(file_name) {
+        debug!(file_name, "rejecting unsafe static file name");
+        return Ok((StatusCode::BAD_REQUEST, "Invalid static file name").into_response());
+    }
+
     let (file, cache_mode) = if let Some(file) = STATIC_FILES.by_hashed_name(file_name) {
         (file, HttpCacheMode::Infinite)
     } else if let Some(file) = STATIC_FILES.by_orig_name(file_name) {
         (file, HttpCacheMode::ShortLived)
     } else {
-        return Ok((StatusCode::NOT_FOUND, "not found".to_owned()).into_response());
+        debug!(file_name, "static file not found");
+        return Ok((
+            StatusCode::NOT_FOUND,
+            "Static file not found (note that file names are case sensitive)",
+        )
+            .into_response());
     };
 
     let content_type = match file_name.rsplit_once(".").map(|(_, ext)| ext).unwrap_or("") {
@@ -43,6 +66,8 @@ pub fn static_file_generic(file_name: &str, headers: HeaderMap) -> HandlerResult
         "xml" => mime::TEXT_XML.as_ref(),
         _ => mime::APPLICATION_OCTET_STREAM.as_ref(),
     };
+    let is_active_content =
+        content_type == mime::IMAGE_SVG.as_ref() || content_type == mime::TEXT_XML.as_ref();
 
     let accepts_gzip = headers
         .get(header::ACCEPT_ENCODING)
@@ -55,7 +80,7 @@ pub fn static_file_generic(file_name: &str, headers: HeaderMap) -> HandlerResult
         })
         .unwrap_or(false);
 
-    Ok(if accepts_gzip {
+    let mut response = if accepts_gzip {
         (
             [
                 (header::CONTENT_TYPE, HeaderValue::from_static(content_type)),
@@ -80,7 +105,22 @@ pub fn static_file_generic(file_name: &str, headers: HeaderMap) -> HandlerResult
             file.original_content,
         )
             .into_response()
-    })
+    };
+    add_vary(response.headers_mut(), header::ACCEPT_ENCODING);
+
+    if is_active_content {
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static(ACTIVE_CONTENT_CSP),
+        );
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_static("inline"),
+        );
+    }
+
+    Ok(response)
 }
 
 #[cfg_attr(not(coverage), tracing::instrument)]
@@ -0,0 +1,25 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::Json;
+use axum::response::IntoResponse;
+use serde::Serialize;
+
+use crate::result::HandlerResult;
+
+#[derive(Serialize)]
+struct Response {
+    version: &'static str,
+    git_hash: &'static str,
+    build_time: &'static str,
+}
+
+#[cfg_attr(not(coverage), tracing::instrument(skip_all))]
+pub async fn version() -> HandlerResult {
+    Ok(Json(Response {
+        version: env!("CARGO_PKG_VERSION"),
+        git_hash: env!("FOOBAR_GIT_HASH"),
+        build_time: env!("FOOBAR_BUILD_TIME"),
+    })
+    .into_response())
+}
@@ -0,0 +1,299 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Verification of JWT bearer tokens issued by external identity provider
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock, Weak};
+use std::time::Duration;
+
+use anyhow::Context;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{DecodingKey, Validation};
+use serde_json::{Map, Value};
+use tracing::{error, info};
+use url::Url;
+
+/// How often key set is refetched from JWKS URL
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Where to get keys for verifying token signatures
+#[derive(Clone, Debug)]
+pub enum JwtKeySource {
+    /// PEM encoded RSA, EC or Ed25519 public key file
+    PublicKey(PathBuf),
+    /// JSON Web Key Set, periodically refetched
+    JwksUrl(Url),
+}
+
+#[derive(Clone, Debug)]
+pub struct JwtConfig {
+    pub key_source: JwtKeySource,
+    /// Required `aud` claim value, if any
+    pub audience: Option<String>,
+    /// Required `iss` claim value, if any
+    pub issuer: Option<String>,
+}
+
+/// Claims of verified token
+///
+/// Added to extensions of requests which passed JWT authentication.
+#[derive(Clone, Debug)]
+pub struct JwtClaims(pub Map<String, Value>);
+
+struct VerificationKey {
+    key_id: Option<String>,
+    key: DecodingKey,
+}
+
+fn load_public_key(pem: &[u8]) -> anyhow::Result<DecodingKey> {
+    DecodingKey::from_rsa_pem(pem)
+        .or_else(|_| DecodingKey::from_ec_pem(pem))
+        .or_else(|_| DecodingKey::from_ed_pem(pem))
+        .context("not a RSA, EC or Ed25519 public key")
+}
+
+fn keys_from_jwks(jwks: &JwkSet) -> anyhow::Result<Vec<VerificationKey>> {
+    jwks.keys
+        .iter()
+        .map(|jwk| {
+            Ok(VerificationKey {
+                key_id: jwk.common.key_id.clone(),
+                key: DecodingKey::from_jwk(jwk).context("cannot use key from key set")?,
+            })
+        })
+        .collect()
+}
+
+async fn fetch_jwks(url: &Url) -> anyhow::Result<Vec<VerificationKey>> {
+    let jwks: JwkSet = reqwest::get(url.clone())
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("cannot fetch key set from {url}"))?
+        .json()
+        .await
+        .with_context(|| format!("cannot parse key set from {url}"))?;
+    keys_from_jwks(&jwks)
+}
+
+pub struct JwtVerifier {
+    keys: RwLock<Vec<VerificationKey>>,
+    audience: Option<String>,
+    issuer: Option<String>,
+}
+
+impl JwtVerifier {
+    /// Load keys, failing if they are not available
+    ///
+    /// With JWKS URL, refreshing task is spawned which lives as long
+    /// as the verifier.
+    pub async fn new(config: &JwtConfig) -> anyhow::Result<Arc<Self>> {
+        let keys = match &config.key_source {
+            JwtKeySource::PublicKey(path) => {
+                let pem = std::fs::read(path)
+                    .with_context(|| format!("cannot read JWT key {}", path.display()))?;
+                let key = load_public_key(&pem)
+                    .with_context(|| format!("cannot load JWT key {}", path.display()))?;
+                vec![VerificationKey { key_id: None, key }]
+            }
+            JwtKeySource::JwksUrl(url) => fetch_jwks(url).await?,
+        };
+
+        let verifier = Arc::new(Self {
+            keys: RwLock::new(keys),
+            audience: config.audience.clone(),
+            issuer: config.issuer.clone(),
+        });
+
+        if let JwtKeySource::JwksUrl(url) = &config.key_source {
+            tokio::spawn(refresh_jwks(Arc::downgrade(&verifier), url.clone()));
+        }
+
+        Ok(verifier)
+    }
+
+    fn validation(&self, header: &jsonwebtoken::Header) -> Validation {
+        // token algorithm is checked against the key type during verification,
+        // so e.g. HMAC algorithms cannot be used with public keys
+        let mut validation = Validation::new(header.alg);
+        validation.validate_nbf = true;
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        validation
+    }
+
+    /// Verify token signature and expiration, returning its claims
+    pub fn verify(&self, token: &str) -> Result<JwtClaims, jsonwebtoken::errors::Error> {
+        use jsonwebtoken::errors::ErrorKind;
+
+        let header = jsonwebtoken::decode_header(token)?;
+        let validation = self.validation(&header);
+        let keys = self.keys.read().expect("keys lock should not be poisoned");
+
+        let mut res = Err(ErrorKind::InvalidSignature.into());
+        for candidate in keys.iter().filter(|candidate| {
+            header.kid.is_none() || candidate.key_id.is_none() || candidate.key_id == header.kid
+        }) {
+            res = jsonwebtoken::decode(token, &candidate.key, &validation);
+            if res.is_ok() {
+                break;
+            }
+        }
+        res.map(|data| JwtClaims(data.claims))
+    }
+}
+
+async fn refresh_jwks(verifier: Weak<JwtVerifier>, url: Url) {
+    let mut interval = tokio::time::interval(JWKS_REFRESH_INTERVAL);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let Some(verifier) = verifier.upgrade() else {
+            return;
+        };
+        match fetch_jwks(&url).await {
+            Ok(keys) => {
+                info!(count = keys.len(), "refreshed JWT key set");
+                *verifier
+                    .keys
+                    .write()
+                    .expect("keys lock should not be poisoned") = keys;
+            }
+            Err(error) => error!(error = format!("{error:#}"), "cannot refresh JWT key set"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+    use serde_json::json;
+
+    use super::*;
+
+    fn now() -> u64 {
+        jsonwebtoken::get_current_timestamp()
+    }
+
+    struct Issuer(rcgen::KeyPair);
+
+    impl Issuer {
+        fn new() -> Self {
+            Self(rcgen::KeyPair::generate().unwrap())
+        }
+
+        fn sign(&self, claims: Value) -> String {
+            let key = EncodingKey::from_ec_pem(self.0.serialize_pem().as_bytes()).unwrap();
+            jsonwebtoken::encode(&Header::new(Algorithm::ES256), &claims, &key).unwrap()
+        }
+
+        fn verifier(&self) -> JwtVerifier {
+            JwtVerifier {
+                keys: RwLock::new(vec![VerificationKey {
+                    key_id: None,
+                    key: load_public_key(self.0.public_key_pem().as_bytes()).unwrap(),
+                }]),
+                audience: None,
+                issuer: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_valid_token() {
+        let issuer = Issuer::new();
+        let token = issuer.sign(json!({"sub": "alice", "exp": now() + 60}));
+        let claims = issuer.verifier().verify(&token).unwrap();
+        assert_eq!(claims.0["sub"], json!("alice"));
+    }
+
+    #[test]
+    fn test_expired_token() {
+        let issuer = Issuer::new();
+        let token = issuer.sign(json!({"sub": "alice", "exp": now() - 3600}));
+        let err = issuer.verifier().verify(&token).unwrap_err();
+        assert_eq!(
+            *err.kind(),
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature
+        );
+    }
+
+    #[test]
+    fn test_token_without_expiration() {
+        let issuer = Issuer::new();
+        let token = issuer.sign(json!({"sub": "alice"}));
+        assert!(issuer.verifier().verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_bad_signature() {
+        let issuer = Issuer::new();
+        let forger = Issuer::new();
+        let token = forger.sign(json!({"sub": "alice", "exp": now() + 60}));
+        let err = issuer.verifier().verify(&token).unwrap_err();
+        assert_eq!(
+            *err.kind(),
+            jsonwebtoken::errors::ErrorKind::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn test_hmac_with_public_key() {
+        // classic attack: sign token with HMAC using public key as the secret
+        let issuer = Issuer::new();
+        let key = EncodingKey::from_secret(issuer.0.public_key_pem().as_bytes());
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &json!({"sub": "alice", "exp": now() + 60}),
+            &key,
+        )
+        .unwrap();
+        assert!(issuer.verifier().verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_audience() {
+        let issuer = Issuer::new();
+        let mut verifier = issuer.verifier();
+        verifier.audience = Some("foobar".to_string());
+        let token = issuer.sign(json!({"aud": "foobar", "exp": now() + 60}));
+        assert!(verifier.verify(&token).is_ok());
+        let token = issuer.sign(json!({"aud": "other", "exp": now() + 60}));
+        assert!(verifier.verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwks_key_selection() {
+        let issuer = Issuer::new();
+        let other = Issuer::new();
+        let verifier = JwtVerifier {
+            keys: RwLock::new(
+                [("other", &other), ("main", &issuer)]
+                    .into_iter()
+                    .map(|(key_id, issuer)| VerificationKey {
+                        key_id: Some(key_id.to_string()),
+                        key: load_public_key(issuer.0.public_key_pem().as_bytes()).unwrap(),
+                    })
+                    .collect(),
+            ),
+            audience: None,
+            issuer: None,
+        };
+
+        let key = EncodingKey::from_ec_pem(issuer.0.serialize_pem().as_bytes()).unwrap();
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some("main".to_string());
+        let token = jsonwebtoken::encode(&header, &json!({"exp": now() + 60}), &key).unwrap();
+        assert!(verifier.verify(&token).is_ok());
+
+        header.kid = Some("other".to_string());
+        let token = jsonwebtoken::encode(&header, &json!({"exp": now() + 60}), &key).unwrap();
+        assert!(verifier.verify(&token).is_err());
+    }
+}
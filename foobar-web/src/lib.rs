@@ -3,38 +3,165 @@
 
 #![feature(coverage_attribute)]
 
+mod admin;
+pub mod base_url;
 pub mod config;
+mod cursor;
+mod features;
+#[cfg(feature = "graphql")]
+mod graphql;
 mod handlers;
+pub mod jwt;
+pub mod metrics_export;
 mod middleware;
+mod openapi;
+mod proxy_protocol;
+mod query;
 mod result;
 mod routes;
+pub mod server;
+mod server_timing;
+pub mod session;
 mod state;
 mod static_files;
+mod template;
+pub mod tls;
+mod vary;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::http::HeaderValue;
 
 use sqlx::PgPool;
+use tower_cookies::CookieManagerLayer;
 use tracing::info;
 
-//use crate::config::AppConfig;
+use foobar_common::{run_migrations, verify_migrations};
+
+use crate::config::AppConfig;
+use crate::jwt::JwtVerifier;
 use crate::routes::Route;
 use crate::state::AppState;
-use crate::static_files::STATIC_FILES;
+use crate::static_files::{STATIC_FILES, preload_link};
+
+pub use crate::state::Readiness;
 
+/// Create application which is ready to serve requests right away
+pub async fn create_app(pool: PgPool, config: AppConfig) -> anyhow::Result<Router> {
+    let readiness = Readiness::default();
+    readiness.set_ready();
+    create_app_with_readiness(pool, config, readiness).await
+}
+
+/// Create application which responds with 503 until `readiness` is set
+///
+/// Infrastructure routes (such as health check) are available
+/// regardless of readiness.
 #[cfg_attr(not(coverage), tracing::instrument(name = "app init", skip_all))]
-pub async fn create_app(pool: PgPool) -> anyhow::Result<Router> {
-    let state = Arc::new(AppState::new(pool.clone()));
+pub async fn create_app_with_readiness(
+    pool: PgPool,
+    config: AppConfig,
+    readiness: Readiness,
+) -> anyhow::Result<Router> {
+    let jwt_verifier = match &config.jwt {
+        Some(jwt_config) => {
+            info!("initializing JWT verification");
+            Some(JwtVerifier::new(jwt_config).await?)
+        }
+        None => None,
+    };
 
     info!("initializing static files");
     let _ = &*STATIC_FILES;
 
+    let preload_links = if config.preload_assets.is_empty() {
+        None
+    } else {
+        let links = config
+            .preload_assets
+            .iter()
+            .map(|file_name| preload_link(file_name))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Some(HeaderValue::try_from(links.join(", "))?)
+    };
+
+    let mut state = AppState::new(pool.clone(), config, readiness);
+    state.jwt_verifier = jwt_verifier;
+    state.preload_links = preload_links;
+    let state = Arc::new(state);
+
     info!("initializing routes");
-    Ok(Route::to_router_with(|router| {
+    let router = Route::to_router_with(|router| {
         router
+            .layer(axum::middleware::from_fn(middleware::db_timeout_middleware))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::server_timing_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::jwt_auth_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::startup_gate_middleware,
+            ))
+            .layer(DefaultBodyLimit::max(
+                state
+                    .config
+                    .max_body_size
+                    .unwrap_or(middleware::DEFAULT_MAX_BODY_SIZE),
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::body_limit_middleware,
+            ))
+            .layer(axum::middleware::from_fn(middleware::framing_middleware))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::concurrency_limit_middleware,
+            ))
+            .layer(axum::middleware::from_fn(
+                middleware::problem_details_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::normalize_path_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::preload_middleware,
+            ))
             .layer(axum::middleware::from_fn(middleware::metrics_middleware))
+            // outside of cookie manager, so cookies it sets are seen
+            .layer(CookieManagerLayer::new())
             .layer(axum::middleware::from_fn(middleware::headers_middleware))
-    })
-    .with_state(state))
+    });
+
+    #[cfg(feature = "graphql")]
+    let router = router.route("/graphql", axum::routing::post(graphql::graphql));
+
+    Ok(router.with_state(state))
+}
+
+/// Bring database schema up to date
+///
+/// With `skip_migrations`, the database is not modified, and only
+/// checked to have all migrations applied, e.g. when it's a read-only
+/// replica or migrations are managed externally.
+pub async fn init_schema(
+    pool: &PgPool,
+    skip_migrations: bool,
+    lock_timeout: Duration,
+) -> anyhow::Result<()> {
+    if skip_migrations {
+        info!("skipping migrations, checking that schema is up to date");
+        verify_migrations(pool).await
+    } else {
+        info!("running migrations");
+        run_migrations(pool, lock_timeout).await
+    }
 }
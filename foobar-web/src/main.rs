@@ -3,12 +3,18 @@
 
 use anyhow::Context;
 use metrics::{counter, gauge};
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Executor, PgPool};
-use tracing::info;
+use sqlx::PgPool;
+use tracing::{error, info};
 
+use foobar_common::{
+    DEFAULT_LOKI_QUEUE_SIZE, JournaldLayer, LogTimer, PoolConfig, StatsdRecorder, connect,
+    install_recorder, is_audit_event, loki_layer, spawn_metrics_collector, spawn_pool_health_probe,
+    syslog_layer,
+};
 use foobar_web::config::Config;
-use foobar_web::create_app;
+use foobar_web::metrics_export::metrics_router;
+use foobar_web::server::serve;
+use foobar_web::{Readiness, create_app_with_readiness, init_schema};
 
 #[allow(unexpected_cfgs)]
 fn collect_tokio_runtime_metrics() {
@@ -65,7 +71,7 @@ fn collect_tokio_runtime_metrics() {
 
 fn init_logging(config: &Config) -> anyhow::Result<()> {
     use tracing_subscriber::Layer;
-    use tracing_subscriber::filter::EnvFilter;
+    use tracing_subscriber::filter::{EnvFilter, filter_fn};
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
 
@@ -74,28 +80,51 @@ fn init_logging(config: &Config) -> anyhow::Result<()> {
     let mut layers = vec![];
 
     if let Some(loki_url) = &config.loki_url {
-        let (layer, task) = tracing_loki::builder()
-            .label("service", "foobar-web")?
-            .build_url(loki_url.clone())
+        let (layer, task) = loki_layer("foobar-web", loki_url, DEFAULT_LOKI_QUEUE_SIZE)
             .context("loki logging initialization failed")?;
-        tokio::spawn(task);
-        layers.push(layer.boxed());
+        tokio::spawn(task.run());
+        layers.push(layer);
+    }
+
+    if config.syslog {
+        layers.push(
+            syslog_layer("foobar-web", config.syslog_facility, config.syslog_addr)
+                .context("syslog logging initialization failed")?,
+        );
     }
 
-    let layer = tracing_subscriber::fmt::Layer::new().with_timer(
-        tracing_subscriber::fmt::time::ChronoLocal::new(String::from("%F %T%.6f")),
-    );
+    if config.journald {
+        layers.push(
+            JournaldLayer::new("foobar-web")
+                .context("journald logging initialization failed")?
+                .boxed(),
+        );
+    }
+
+    let timer = LogTimer::new(&config.log_timestamp_format, config.log_timestamp_utc)?;
+    let layer = tracing_subscriber::fmt::Layer::new().with_timer(timer.clone());
 
     if let Some(log_directory) = &config.log_directory {
         use tracing_appender::rolling::{RollingFileAppender, Rotation};
-        let logfile = RollingFileAppender::builder()
-            .rotation(Rotation::DAILY)
-            .filename_prefix("foobar-web.log")
-            .max_log_files(14)
-            .build(log_directory)
-            .context("logging initialization failed")?;
-        layers.push(layer.with_writer(logfile).boxed());
-    } else {
+        let open_log_file = |prefix| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(prefix)
+                .max_log_files(14)
+                .build(log_directory)
+                .context("logging initialization failed")
+        };
+        if config.audit_log {
+            layers.push(
+                tracing_subscriber::fmt::Layer::new()
+                    .with_timer(timer)
+                    .with_writer(open_log_file("foobar-web.audit.log")?)
+                    .with_filter(filter_fn(is_audit_event))
+                    .boxed(),
+            );
+        }
+        layers.push(layer.with_writer(open_log_file("foobar-web.log")?).boxed());
+    } else if config.log_to_stdout() {
         layers.push(layer.boxed());
     }
 
@@ -107,7 +136,7 @@ fn init_logging(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn init_metrics(config: &Config) -> anyhow::Result<()> {
+async fn init_metrics(config: &Config) -> anyhow::Result<()> {
     if let Some(socket_addr) = &config.prometheus_export {
         info!("initializing prometheus exporter");
         use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
@@ -122,7 +151,7 @@ fn init_metrics(config: &Config) -> anyhow::Result<()> {
             262144., 524288., 1048576., 2097152.,
         ];
 
-        PrometheusBuilder::new()
+        let recorder = PrometheusBuilder::new()
             .set_buckets_for_metric(
                 Matcher::Suffix("_duration_seconds".to_string()),
                 DURATION_SECONDS_BUCKETS,
@@ -133,58 +162,104 @@ fn init_metrics(config: &Config) -> anyhow::Result<()> {
                 SIZE_BYTES_BUCKETS,
             )
             .unwrap()
-            .with_http_listener(*socket_addr)
-            .install()
-            .context("prometheus exporter initialization failed")?;
-
-        let collector = metrics_process::Collector::default();
-        collector.describe();
+            .build_recorder();
+        let handle = recorder.handle();
 
+        let listener = tokio::net::TcpListener::bind(socket_addr)
+            .await
+            .context("prometheus exporter initialization failed")?;
+        let router = metrics_router(handle.clone(), config.prometheus_export_auth.as_deref());
         tokio::spawn(async move {
-            loop {
-                collector.collect();
-                collect_tokio_runtime_metrics();
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            if let Err(err) = axum::serve(listener, router).await {
+                error!("prometheus exporter failed: {err}");
             }
         });
+        spawn_metrics_collector(std::time::Duration::from_secs(5), move || {
+            handle.run_upkeep();
+        });
+
+        install_recorder(recorder, config.metrics_prefix.as_deref())?;
+    } else if let Some(statsd_addr) = &config.statsd_addr {
+        info!("initializing statsd exporter");
+        let recorder =
+            StatsdRecorder::new(*statsd_addr).context("statsd exporter initialization failed")?;
+        install_recorder(recorder, config.metrics_prefix.as_deref())?;
+    } else {
+        return Ok(());
     }
 
+    let collector = metrics_process::Collector::default();
+    collector.describe();
+
+    spawn_metrics_collector(std::time::Duration::from_secs(5), move || {
+        collector.collect();
+        collect_tokio_runtime_metrics();
+    });
+
     Ok(())
 }
 
 async fn init_database(config: &Config) -> anyhow::Result<PgPool> {
     info!("initializing database pool");
-    let pool = PgPoolOptions::new()
-        .after_connect(|conn, _meta| {
-            Box::pin(async move {
-                conn.execute("SET application_name = 'foobar-web'").await?;
-                Ok(())
-            })
-        })
-        .connect(&config.dsn)
-        .await
-        .context("error creating PostgreSQL connection pool")?;
+    connect(
+        &config.dsn,
+        &PoolConfig {
+            application_name: "foobar-web",
+            statement_cache_capacity: config.statement_cache_capacity,
+        },
+    )
+    .await
+}
+
+async fn shutdown_signal() {
+    use tokio::signal::unix::{SignalKind, signal};
 
-    Ok(pool)
+    let mut sigterm =
+        signal(SignalKind::terminate()).expect("SIGTERM handler should be installable");
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = sigterm.recv() => {}
+    }
+    info!("shutting down");
 }
 
 async fn async_main() -> anyhow::Result<()> {
     let config = Config::parse().with_context(|| "failed to process configuration")?;
 
     init_logging(&config).with_context(|| "failed to init logging")?;
-    init_metrics(&config).with_context(|| "failed to init metrics")?;
+    config.log_startup_banner();
+    info!("effective configuration:\n{}", config.to_redacted_toml());
+    init_metrics(&config)
+        .await
+        .with_context(|| "failed to init metrics")?;
     let pool = init_database(&config)
         .await
         .with_context(|| "failed to init database")?;
+    spawn_pool_health_probe(pool.clone(), std::time::Duration::from_secs(10));
 
     info!("initializing application");
-    let app = create_app(pool).await?;
+    let readiness = Readiness::default();
+    let app =
+        create_app_with_readiness(pool.clone(), config.app_config(), readiness.clone()).await?;
 
-    info!("listening");
-    let listener = tokio::net::TcpListener::bind(&config.listen).await.unwrap();
-    axum::serve(listener, app)
-        .await
-        .context("error starting HTTP server")
+    info!(listen = %config.listen, "listening");
+    let server_config = config.server_config();
+    let server = tokio::spawn(async move { serve(&server_config, app, shutdown_signal()).await });
+
+    // the app responds with 503 until migrations are complete
+    init_schema(
+        &pool,
+        config.skip_migrations,
+        config.migration_lock_timeout(),
+    )
+    .await?;
+
+    info!("application is ready");
+    readiness.set_ready();
+
+    server.await?.context("error running HTTP server")?;
+    info!("shut down");
+    Ok(())
 }
 
 fn main() -> anyhow::Result<()> {
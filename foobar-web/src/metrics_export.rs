@@ -0,0 +1,379 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Endpoint for scraping metrics by Prometheus
+//!
+//! Served on a dedicated address, separately from the application.
+//! Besides Prometheus text format, metrics may be requested as JSON
+//! with `?format=json`, which is easier to inspect by humans.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde::{Deserialize, Serialize};
+
+use foobar_common::check_basic_auth;
+
+/// Content type of Prometheus text exposition format
+///
+/// Some scrapers are strict about it, so it's specified exactly,
+/// including format version.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+struct MetricsState {
+    handle: PrometheusHandle,
+    /// Base64 encoded `user:password`, if basic auth is required
+    credentials: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    format: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct Bucket {
+    le: String,
+    count: f64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct Quantile {
+    quantile: String,
+    value: f64,
+}
+
+/// Single labelled series of a metric
+///
+/// Histograms and summaries, which consist of multiple samples in the
+/// text format, are represented as a single series.
+#[derive(Serialize, Default, Debug, PartialEq)]
+struct Series {
+    labels: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    buckets: Vec<Bucket>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    quantiles: Vec<Quantile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<f64>,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq)]
+struct MetricFamily {
+    name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    metric_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<String>,
+    series: Vec<Series>,
+}
+
+/// Parse labels of a sample, starting after the opening brace
+///
+/// Returns labels and the rest of the line after the closing brace.
+fn parse_labels(mut rest: &str) -> Option<(BTreeMap<String, String>, &str)> {
+    let mut labels = BTreeMap::new();
+    loop {
+        rest = rest.trim_start_matches([' ', ',']);
+        if let Some(rest) = rest.strip_prefix('}') {
+            return Some((labels, rest));
+        }
+        let (name, tail) = rest.split_once("=\"")?;
+        let mut value = String::new();
+        let mut chars = tail.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    escaped => value.push(escaped),
+                },
+                (pos, '"') => break pos,
+                (_, c) => value.push(c),
+            }
+        };
+        labels.insert(name.trim().to_string(), value);
+        rest = &tail[end + 1..];
+    }
+}
+
+/// Convert Prometheus text exposition into metric families
+///
+/// Only handles the subset of the format produced by the exporter;
+/// malformed lines are skipped.
+fn parse_exposition(text: &str) -> Vec<MetricFamily> {
+    fn family<'a>(families: &'a mut Vec<MetricFamily>, name: &str) -> &'a mut MetricFamily {
+        if let Some(pos) = families.iter().position(|family| family.name == name) {
+            &mut families[pos]
+        } else {
+            families.push(MetricFamily {
+                name: name.to_string(),
+                ..Default::default()
+            });
+            families.last_mut().expect("family was just added")
+        }
+    }
+
+    let mut families: Vec<MetricFamily> = vec![];
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("# HELP ") {
+            let (name, help) = rest.split_once(' ').unwrap_or((rest, ""));
+            family(&mut families, name).help = Some(help.to_string());
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("# TYPE ") {
+            let (name, metric_type) = rest.split_once(' ').unwrap_or((rest, ""));
+            family(&mut families, name).metric_type = Some(metric_type.to_string());
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+        let (sample_name, rest) = line.split_at(name_end);
+        let (mut labels, rest) = match rest.strip_prefix('{') {
+            Some(rest) => match parse_labels(rest) {
+                Some(parsed) => parsed,
+                None => continue,
+            },
+            None => (BTreeMap::new(), rest),
+        };
+        let Some(value) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<f64>().ok())
+        else {
+            continue;
+        };
+
+        // samples of histograms and summaries have suffixed names
+        let (family_name, suffix) = ["_bucket", "_sum", "_count"]
+            .into_iter()
+            .find_map(|suffix| {
+                let family_name = sample_name.strip_suffix(suffix)?;
+                families
+                    .iter()
+                    .any(|family| family.name == family_name)
+                    .then_some((family_name, suffix))
+            })
+            .unwrap_or((sample_name, ""));
+
+        let le = labels.remove("le");
+        let quantile = labels.remove("quantile");
+        let family = family(&mut families, family_name);
+        let series = match family
+            .series
+            .iter()
+            .position(|series| series.labels == labels)
+        {
+            Some(pos) => &mut family.series[pos],
+            None => {
+                family.series.push(Series {
+                    labels,
+                    ..Default::default()
+                });
+                family.series.last_mut().expect("series was just added")
+            }
+        };
+
+        match (suffix, le, quantile) {
+            ("_bucket", Some(le), _) => series.buckets.push(Bucket { le, count: value }),
+            ("_sum", ..) => series.sum = Some(value),
+            ("_count", ..) => series.count = Some(value),
+            (_, _, Some(quantile)) => series.quantiles.push(Quantile { quantile, value }),
+            _ => series.value = Some(value),
+        }
+    }
+    families
+}
+
+async fn metrics(
+    State(state): State<Arc<MetricsState>>,
+    Query(query): Query<QueryParams>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(credentials) = &state.credentials {
+        let authorization = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        if !check_basic_auth(authorization, credentials) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, r#"Basic realm="metrics""#)],
+            )
+                .into_response();
+        }
+    }
+
+    let as_json = match query.format.as_deref() {
+        None | Some("prometheus") => false,
+        Some("json") => true,
+        Some(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "Unknown format, expected prometheus or json",
+            )
+                .into_response();
+        }
+    };
+
+    let handle = state.handle.clone();
+    let body = tokio::task::spawn_blocking(move || handle.render())
+        .await
+        .expect("metrics rendering should not panic");
+
+    if as_json {
+        let families = parse_exposition(&body);
+        return (
+            [(header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())],
+            serde_json::to_string_pretty(&families).expect("metrics should be serializable"),
+        )
+            .into_response();
+    }
+
+    // HEAD is routed here as well, with the body stripped by axum
+    // while keeping headers such as Content-Length intact
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response()
+}
+
+/// Create router serving metrics at `/metrics`
+///
+/// If `credentials` (in `user:password` form) are specified, requests
+/// must carry these in `Authorization: Basic` header.
+pub fn metrics_router(handle: PrometheusHandle, credentials: Option<&str>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(Arc::new(MetricsState {
+            handle,
+            credentials: credentials.map(|credentials| STANDARD.encode(credentials)),
+        }))
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exposition() {
+        let text = indoc::indoc! {r#"
+            # HELP requests_total Number of requests
+            # TYPE requests_total counter
+            requests_total{route="/",status="200"} 3
+            requests_total{route="/item/{id}",status="404"} 1
+
+            # TYPE temperature gauge
+            temperature -1.5
+
+            # TYPE duration_seconds histogram
+            duration_seconds_bucket{route="/",le="0.1"} 2
+            duration_seconds_bucket{route="/",le="+Inf"} 3
+            duration_seconds_sum{route="/"} 0.5
+            duration_seconds_count{route="/"} 3
+
+            # TYPE size summary
+            size{quantile="0.5"} 10
+            size_sum 30
+            size_count 2
+        "#};
+
+        let labels = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+
+        assert_eq!(
+            parse_exposition(text),
+            vec![
+                MetricFamily {
+                    name: "requests_total".to_string(),
+                    metric_type: Some("counter".to_string()),
+                    help: Some("Number of requests".to_string()),
+                    series: vec![
+                        Series {
+                            labels: labels(&[("route", "/"), ("status", "200")]),
+                            value: Some(3.0),
+                            ..Default::default()
+                        },
+                        Series {
+                            labels: labels(&[("route", "/item/{id}"), ("status", "404")]),
+                            value: Some(1.0),
+                            ..Default::default()
+                        },
+                    ],
+                },
+                MetricFamily {
+                    name: "temperature".to_string(),
+                    metric_type: Some("gauge".to_string()),
+                    help: None,
+                    series: vec![Series {
+                        value: Some(-1.5),
+                        ..Default::default()
+                    }],
+                },
+                MetricFamily {
+                    name: "duration_seconds".to_string(),
+                    metric_type: Some("histogram".to_string()),
+                    help: None,
+                    series: vec![Series {
+                        labels: labels(&[("route", "/")]),
+                        buckets: vec![
+                            Bucket {
+                                le: "0.1".to_string(),
+                                count: 2.0
+                            },
+                            Bucket {
+                                le: "+Inf".to_string(),
+                                count: 3.0
+                            },
+                        ],
+                        sum: Some(0.5),
+                        count: Some(3.0),
+                        ..Default::default()
+                    }],
+                },
+                MetricFamily {
+                    name: "size".to_string(),
+                    metric_type: Some("summary".to_string()),
+                    help: None,
+                    series: vec![Series {
+                        quantiles: vec![Quantile {
+                            quantile: "0.5".to_string(),
+                            value: 10.0
+                        }],
+                        sum: Some(30.0),
+                        count: Some(2.0),
+                        ..Default::default()
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_labels_escaped() {
+        let (labels, rest) = parse_labels(r#"a="x\"y\\z\n",b="}"} 1"#).unwrap();
+        assert_eq!(labels["a"], "x\"y\\z\n");
+        assert_eq!(labels["b"], "}");
+        assert_eq!(rest, " 1");
+    }
+
+    #[test]
+    fn test_parse_exposition_malformed() {
+        assert_eq!(parse_exposition("foo{a=\"b 1\nbar notanumber\n"), vec![]);
+    }
+}
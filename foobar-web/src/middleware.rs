@@ -1,8 +1,28 @@
 // SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+pub mod body_limit;
+pub mod concurrency_limit;
+pub mod db_timeout;
+pub mod framing;
 pub mod headers;
+pub mod jwt_auth;
 pub mod metrics;
+pub mod normalize_path;
+pub mod preload;
+pub mod problem_details;
+pub mod server_timing;
+pub mod startup_gate;
 
+pub use body_limit::*;
+pub use concurrency_limit::*;
+pub use db_timeout::*;
+pub use framing::*;
 pub use headers::*;
+pub use jwt_auth::*;
 pub use metrics::*;
+pub use normalize_path::*;
+pub use preload::*;
+pub use problem_details::*;
+pub use server_timing::*;
+pub use startup_gate::*;
@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::state::AppState;
+
+/// Default maximal size of request body
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Reject requests which declare body larger than allowed with 413
+///
+/// Bodies of unknown size (chunked ones) are limited when read by
+/// extractors, through `DefaultBodyLimit` set up along with this
+/// middleware.
+pub async fn body_limit_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let max_size = state.config.max_body_size.unwrap_or(DEFAULT_MAX_BODY_SIZE);
+    let content_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    if content_length.is_some_and(|length| length > max_size as u64) {
+        (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response()
+    } else {
+        next.run(request).await
+    }
+}
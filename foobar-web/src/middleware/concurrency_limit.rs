@@ -0,0 +1,53 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use metrics::counter;
+
+use crate::routes::MyRoute;
+use crate::state::AppState;
+
+const RETRY_AFTER_SECONDS: &str = "1";
+
+/// Shed requests above concurrency limit with 503
+///
+/// Excess requests are not queued, so at saturation clients get
+/// immediate responses instead of piling up and timing out. Routes
+/// marked as infrastructure (such as health check) are not limited.
+pub async fn concurrency_limit_middleware(
+    State(state): State<Arc<AppState>>,
+    route: Option<MyRoute>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = &state.request_limiter else {
+        return next.run(request).await;
+    };
+    let is_infrastructure = route
+        .map(|route| route.props().infrastructure)
+        .unwrap_or_default();
+    if is_infrastructure {
+        return next.run(request).await;
+    }
+
+    match limiter.clone().try_acquire_owned() {
+        Ok(_permit) => next.run(request).await,
+        Err(_) => {
+            counter!("foobar_web_requests_shed_total").increment(1);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(
+                    header::RETRY_AFTER,
+                    HeaderValue::from_static(RETRY_AFTER_SECONDS),
+                )],
+                "Service overloaded, please retry later",
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use metrics::counter;
+use tracing::warn;
+
+use crate::routes::MyRoute;
+
+/// Limit handler run time to the route's database timeout
+///
+/// Handlers spend most of their time in database queries, so
+/// instead of wrapping each query, the whole handler is limited.
+/// When the timeout expires, handler future is dropped, which
+/// cancels the running query.
+pub async fn db_timeout_middleware(
+    route: Option<MyRoute>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(db_timeout) = route.and_then(|route| route.props().db_timeout) else {
+        return next.run(request).await;
+    };
+
+    match tokio::time::timeout(db_timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!(timeout = ?db_timeout, "request timed out waiting for database");
+            counter!("foobar_db_timeouts_total").increment(1);
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                "Timed out waiting for database",
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::extract::Request;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Check that request body framing is unambiguous
+///
+/// Requests with both `Content-Length` and `Transfer-Encoding`, or
+/// with multiple or malformed `Content-Length`, may be interpreted
+/// differently by proxies and the application, which allows request
+/// smuggling.
+fn check_framing(headers: &HeaderMap) -> Result<(), &'static str> {
+    let mut lengths = headers.get_all(header::CONTENT_LENGTH).iter();
+    let Some(length) = lengths.next() else {
+        return Ok(());
+    };
+    if headers.contains_key(header::TRANSFER_ENCODING) {
+        return Err("Both Content-Length and Transfer-Encoding specified");
+    }
+    if lengths.next().is_some() {
+        return Err("Multiple Content-Length specified");
+    }
+    // plain parse would also accept leading sign
+    length
+        .to_str()
+        .ok()
+        .filter(|length| length.bytes().all(|byte| byte.is_ascii_digit()))
+        .and_then(|length| length.parse::<u64>().ok())
+        .map(|_| ())
+        .ok_or("Invalid Content-Length")
+}
+
+/// Reject requests with ambiguous body framing with 400
+///
+/// HTTP server already rejects most of such requests, but the policy
+/// is enforced here as well so it does not depend on its version.
+pub async fn framing_middleware(request: Request, next: Next) -> Response {
+    match check_framing(request.headers()) {
+        Ok(()) => next.run(request).await,
+        Err(reason) => (StatusCode::BAD_REQUEST, reason).into_response(),
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(check_framing(&headers(&[])).is_ok());
+        assert!(check_framing(&headers(&[(header::CONTENT_LENGTH, "0")])).is_ok());
+        assert!(check_framing(&headers(&[(header::CONTENT_LENGTH, "123")])).is_ok());
+        assert!(check_framing(&headers(&[(header::TRANSFER_ENCODING, "chunked")])).is_ok());
+    }
+
+    #[test]
+    fn test_conflicting() {
+        assert!(
+            check_framing(&headers(&[
+                (header::CONTENT_LENGTH, "5"),
+                (header::TRANSFER_ENCODING, "chunked")
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_length() {
+        assert!(
+            check_framing(&headers(&[
+                (header::CONTENT_LENGTH, "5"),
+                (header::CONTENT_LENGTH, "5")
+            ]))
+            .is_err()
+        );
+        assert!(
+            check_framing(&headers(&[
+                (header::CONTENT_LENGTH, "5"),
+                (header::CONTENT_LENGTH, "6")
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        for value in [
+            "",
+            "+5",
+            "-5",
+            "5, 5",
+            "0x10",
+            " 5",
+            "99999999999999999999999",
+        ] {
+            assert!(
+                check_framing(&headers(&[(header::CONTENT_LENGTH, value)])).is_err(),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+}
@@ -2,27 +2,58 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use axum::extract::Request;
-use axum::http::header::HeaderValue;
+use axum::http::header::{self, HeaderValue};
 use axum::middleware::Next;
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
+use chrono::DateTime;
+use tracing::info;
 
-use crate::routes::MyRoute;
+use crate::routes::{Deprecation, MyRoute, RouteProps};
+
+fn add_deprecation_headers(response: &mut Response, deprecation: &Deprecation) {
+    let headers = response.headers_mut();
+    headers.insert(
+        "Deprecation",
+        HeaderValue::try_from(format!("@{}", deprecation.since))
+            .expect("deprecation header value should be valid"),
+    );
+    if let Some(sunset) = deprecation
+        .sunset
+        .and_then(|sunset| DateTime::from_timestamp(sunset, 0))
+    {
+        headers.insert(
+            "Sunset",
+            HeaderValue::try_from(sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+                .expect("sunset header value should be valid"),
+        );
+    }
+}
 
 pub async fn headers_middleware(
     route: Option<MyRoute>,
     request: Request,
     next: Next,
 ) -> impl IntoResponse {
-    let allow_embedding = route
-        .map(|route| route.props().allow_embedding)
-        .unwrap_or_default();
+    let props = route.map(|route| route.props()).unwrap_or_default();
+    add_headers(props, request, next).await
+}
+
+async fn add_headers(props: RouteProps, request: Request, next: Next) -> Response {
+    if props.deprecation.is_some() {
+        info!(path = request.uri().path(), "deprecated route requested");
+    }
     let mut response = next.run(request).await;
 
     response.headers_mut().insert(
         "X-Content-Type-Options",
         HeaderValue::from_static("nosniff"),
     );
-    if !allow_embedding {
+    if response
+        .headers()
+        .contains_key(header::CONTENT_SECURITY_POLICY)
+    {
+        // more specific policy set by the handler
+    } else if !props.allow_embedding {
         response.headers_mut().insert("Content-Security-Policy", HeaderValue::from_static("default-src 'none'; style-src 'self'; script-src 'self'; img-src 'self'; font-src 'self'; frame-ancestors 'none'; base-uri 'none'; form-action 'self'"));
         response
             .headers_mut()
@@ -31,6 +62,32 @@ pub async fn headers_middleware(
         // relaxed headers to allow some embedding cases, see https://github.com/repology/repology-webapp/issues/175
         response.headers_mut().insert("Content-Security-Policy", HeaderValue::from_static("default-src 'none'; style-src 'self'; script-src 'self'; img-src 'self'; font-src 'self'; frame-ancestors *; base-uri 'none'; form-action 'self'"));
     }
+    if response.headers().contains_key(header::SET_COOKIE) {
+        // response carries client specific state, so it must not be
+        // stored by shared caches, whatever the route allows
+        let is_private = response
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("private") || value.contains("no-store"));
+        if !is_private {
+            response.headers_mut().insert(
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("private, no-cache"),
+            );
+        }
+    } else if let Some(cache_control) = props.cache_control
+        && response.status().is_success()
+        && !response.headers().contains_key(header::CACHE_CONTROL)
+    {
+        response.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(cache_control),
+        );
+    }
+    if let Some(deprecation) = &props.deprecation {
+        add_deprecation_headers(&mut response, deprecation);
+    }
     // NOTE: Strict-Transport-Security must be set where HTTPS is terminated, e.g. nginx
 
     // XXX: Uncomment if desired, e.g. the site does not contain private
@@ -39,3 +96,76 @@ pub async fn headers_middleware(
 
     response
 }
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use axum::Router;
+    use axum::routing::get;
+    use axum_test::TestServer;
+
+    use super::*;
+
+    #[test]
+    fn test_deprecation_headers() {
+        let mut response = Response::default();
+        add_deprecation_headers(
+            &mut response,
+            &Deprecation {
+                since: 1767225600,
+                sunset: Some(1798761600),
+            },
+        );
+        assert_eq!(response.headers()["deprecation"], "@1767225600");
+        assert_eq!(
+            response.headers()["sunset"],
+            "Fri, 01 Jan 2027 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_deprecation_headers_without_sunset() {
+        let mut response = Response::default();
+        add_deprecation_headers(
+            &mut response,
+            &Deprecation {
+                since: 1767225600,
+                sunset: None,
+            },
+        );
+        assert_eq!(response.headers()["deprecation"], "@1767225600");
+        assert!(!response.headers().contains_key("sunset"));
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_route() {
+        let props = RouteProps {
+            deprecation: Some(Deprecation {
+                since: 1767225600,
+                sunset: Some(1798761600),
+            }),
+            ..Default::default()
+        };
+        let router = Router::new()
+            .route("/deprecated", get(|| async { "deprecated" }))
+            .layer(axum::middleware::from_fn(move |request, next| {
+                add_headers(props, request, next)
+            }))
+            .merge(
+                Router::new()
+                    .route("/current", get(|| async { "current" }))
+                    .layer(axum::middleware::from_fn(headers_middleware)),
+            );
+        let server = TestServer::new(router);
+
+        let response = server.get("/deprecated").await;
+        response.assert_status_ok();
+        response.assert_header("deprecation", "@1767225600");
+        response.assert_header("sunset", "Fri, 01 Jan 2027 00:00:00 GMT");
+
+        let response = server.get("/current").await;
+        response.assert_status_ok();
+        assert!(response.maybe_header("deprecation").is_none());
+        assert!(response.maybe_header("sunset").is_none());
+    }
+}
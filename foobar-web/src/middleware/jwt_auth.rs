@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use tracing::debug;
+
+use crate::routes::MyRoute;
+use crate::state::AppState;
+
+/// Require valid JWT bearer token for routes which opt in
+///
+/// Claims of the verified token are available to handlers as
+/// `Extension<JwtClaims>`. When JWT verification is not configured,
+/// such routes are disabled and respond with 404.
+pub async fn jwt_auth_middleware(
+    State(state): State<Arc<AppState>>,
+    route: Option<MyRoute>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let jwt_required = route
+        .map(|route| route.props().jwt_required)
+        .unwrap_or_default();
+    if !jwt_required {
+        return next.run(request).await;
+    }
+
+    let Some(verifier) = &state.jwt_verifier else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match token.map(|token| verifier.verify(token)) {
+        Some(Ok(claims)) => {
+            request.extensions_mut().insert(claims);
+            next.run(request).await
+        }
+        Some(Err(error)) => {
+            debug!(%error, "JWT verification failed");
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer error=\"invalid_token\"")],
+            )
+                .into_response()
+        }
+        None => (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response(),
+    }
+}
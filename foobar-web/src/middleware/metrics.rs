@@ -1,31 +1,88 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
 use axum::body::HttpBody;
 use axum::extract::Request;
+use axum::http::StatusCode;
 use axum::middleware::Next;
 use axum::response::IntoResponse;
 use metrics::{counter, histogram};
 
 use crate::routes::MyRoute;
 
+/// Status recorded for requests aborted by the client, as in nginx
+const CLIENT_CLOSED_REQUEST: &str = "499";
+
+/// Route label for requests which were not matched to any route
+const UNKNOWN_ROUTE: &str = "???";
+
+/// Flag set by the server when it abandons a request on timeout
+///
+/// Passed in request extensions, so metrics of the dropped request
+/// are recorded with 503, as sent by the server, instead of 499.
+#[derive(Clone, Default)]
+pub struct RequestTimedOut(Arc<AtomicBool>);
+
+impl RequestTimedOut {
+    pub fn set(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn is_set(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Records request metrics when dropped
+///
+/// If the request future is dropped before the response is produced,
+/// which happens when client disconnects, it's recorded with 499
+/// status so it does not count as a server error.
+struct RequestMetrics {
+    route_name: &'static str,
+    start: Instant,
+    status: Option<String>,
+    timed_out: Option<RequestTimedOut>,
+}
+
+impl Drop for RequestMetrics {
+    fn drop(&mut self) {
+        let latency = self.start.elapsed().as_secs_f64();
+        let status = self.status.take().unwrap_or_else(|| {
+            if self.timed_out.as_ref().is_some_and(RequestTimedOut::is_set) {
+                StatusCode::SERVICE_UNAVAILABLE.as_str().to_string()
+            } else {
+                CLIENT_CLOSED_REQUEST.to_string()
+            }
+        });
+
+        counter!("foobar_web_http_requests_total", "route" => self.route_name, "status" => status)
+            .increment(1);
+        histogram!("foobar_web_http_requests_duration_seconds", "route" => self.route_name)
+            .record(latency);
+    }
+}
+
 pub async fn metrics_middleware(
     route: Option<MyRoute>,
     request: Request,
     next: Next,
 ) -> impl IntoResponse {
-    let start = Instant::now();
-    let response = next.run(request).await;
-    let latency = start.elapsed().as_secs_f64();
+    let route_name = route.map(|route| route.path()).unwrap_or(UNKNOWN_ROUTE);
+    let mut request_metrics = RequestMetrics {
+        route_name,
+        start: Instant::now(),
+        status: None,
+        timed_out: request.extensions().get::<RequestTimedOut>().cloned(),
+    };
 
-    let route_name = route.map(|route| route.path()).unwrap_or("???");
-    let status = response.status().as_u16().to_string();
+    let response = next.run(request).await;
 
-    counter!("foobar_web_http_requests_total", "route" => route_name, "status" => status)
-        .increment(1);
-    histogram!("foobar_web_http_requests_duration_seconds", "route" => route_name).record(latency);
+    request_metrics.status = Some(response.status().as_u16().to_string());
 
     if let Some(body_size) = response.body().size_hint().exact() {
         histogram!("foobar_web_http_response_size_bytes", "route" => route_name)
@@ -34,3 +91,99 @@ pub async fn metrics_middleware(
 
     response
 }
+
+/// Record request which was rejected by the server before routing
+///
+/// This is the case for malformed requests, and requests which
+/// headers were not received in time.
+pub fn record_rejected_request(status: StatusCode) {
+    counter!("foobar_web_http_requests_total", "route" => UNKNOWN_ROUTE, "status" => status.as_str().to_string())
+        .increment(1);
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::Router;
+    use axum::body::Body;
+    use axum::routing::get;
+    use metrics_util::debugging::DebuggingRecorder;
+    use tower::ServiceExt;
+
+    use foobar_common::testing::{MetricsSnapshot, counter_values_by_label};
+
+    use super::*;
+
+    fn requests_by_status(recorder: &DebuggingRecorder) -> Vec<(String, u64)> {
+        counter_values_by_label(recorder, "foobar_web_http_requests_total", "status")
+    }
+
+    #[test]
+    fn test_client_disconnect() {
+        let recorder = DebuggingRecorder::new();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let app = Router::new()
+                    .route("/ok", get(|| async { "ok" }))
+                    .route("/slow", get(std::future::pending::<()>))
+                    .layer(axum::middleware::from_fn(metrics_middleware));
+
+                let request = |uri| Request::get(uri).body(Body::empty()).unwrap();
+
+                app.clone().oneshot(request("/ok")).await.unwrap();
+
+                // dropping the request future is what happens when client disconnects
+                let res =
+                    tokio::time::timeout(Duration::from_millis(10), app.oneshot(request("/slow")))
+                        .await;
+                assert!(res.is_err());
+            })
+        });
+
+        let snapshot = MetricsSnapshot::take(&recorder);
+        assert_eq!(
+            snapshot.counter_values_by_label("foobar_web_http_requests_total", "status"),
+            vec![("200".to_string(), 1), ("499".to_string(), 1)]
+        );
+        // latency of abandoned request covers the time until it was dropped
+        let latencies = snapshot.histogram_values("foobar_web_http_requests_duration_seconds");
+        assert_eq!(latencies.len(), 2);
+        assert!(latencies.iter().any(|&latency| latency >= 0.01));
+    }
+
+    #[test]
+    fn test_server_timeout() {
+        let recorder = DebuggingRecorder::new();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let app = Router::new()
+                    .route("/slow", get(std::future::pending::<()>))
+                    .layer(axum::middleware::from_fn(metrics_middleware));
+
+                let timed_out = RequestTimedOut::default();
+                let mut request = Request::get("/slow").body(Body::empty()).unwrap();
+                request.extensions_mut().insert(timed_out.clone());
+
+                // server flags the timeout before dropping the request future
+                let mut response = std::pin::pin!(app.oneshot(request));
+                let res = tokio::time::timeout(Duration::from_millis(10), response.as_mut()).await;
+                assert!(res.is_err());
+                timed_out.set();
+            })
+        });
+
+        assert_eq!(requests_by_status(&recorder), vec![("503".to_string(), 1)]);
+    }
+}
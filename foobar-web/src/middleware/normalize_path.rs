@@ -0,0 +1,208 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Redirect, Response};
+
+use crate::config::PathRedirect;
+use crate::routes::{Route, TrailingSlash};
+use crate::state::AppState;
+
+/// Canonicalize request path
+///
+/// Collapses duplicate slashes and resolves `.` and `..` segments
+/// (never going above the root). Path is processed in its raw form,
+/// so percent-encoded characters (including `%2F`) are left as is
+/// and never become path separators. Trailing slash is preserved.
+///
+/// Returns `None` if the path is already canonical.
+fn canonicalize_path(path: &str) -> Option<String> {
+    let mut segments: Vec<&str> = vec![];
+    let mut trailing_slash = false;
+    for segment in path.split('/').skip(1) {
+        trailing_slash = matches!(segment, "" | "." | "..");
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let mut canonical = String::with_capacity(path.len());
+    for segment in &segments {
+        canonical.push('/');
+        canonical.push_str(segment);
+    }
+    if trailing_slash || segments.is_empty() {
+        canonical.push('/');
+    }
+
+    (canonical != path).then_some(canonical)
+}
+
+/// Check whether path matches route path pattern such as `/item/{id}`
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let mut path_segments = path.split('/');
+    for pattern_segment in pattern.split('/') {
+        if pattern_segment.starts_with("{*") {
+            return path_segments
+                .next()
+                .is_some_and(|segment| !segment.is_empty());
+        }
+        match path_segments.next() {
+            Some(segment) if pattern_segment.starts_with('{') && !segment.is_empty() => {}
+            Some(segment) if segment == pattern_segment => {}
+            _ => return false,
+        }
+    }
+    path_segments.next().is_none()
+}
+
+/// Find path differing in trailing slash which should be redirected to
+///
+/// That is the case when the path itself does not match any route, but
+/// the alternative does, and the policy of matched route allows redirect.
+fn trailing_slash_redirect<'a>(
+    path: &str,
+    mut routes: impl Iterator<Item = (&'a str, TrailingSlash)> + Clone,
+) -> Option<String> {
+    if path == "/"
+        || routes
+            .clone()
+            .any(|(pattern, _)| matches_pattern(pattern, path))
+    {
+        return None;
+    }
+    let (alternative, policy) = match path.strip_suffix('/') {
+        Some(stripped) => (stripped.to_string(), TrailingSlash::Strip),
+        None => (format!("{path}/"), TrailingSlash::Require),
+    };
+    routes
+        .any(|(pattern, route_policy)| {
+            route_policy == policy && matches_pattern(pattern, &alternative)
+        })
+        .then_some(alternative)
+}
+
+/// Redirect requests to canonical paths
+///
+/// Besides canonicalizing path, redirects paths which differ from
+/// some route path in trailing slash according to the route's
+/// [`TrailingSlash`] policy. Redirects are permanent or temporary
+/// depending on configuration.
+pub async fn normalize_path_middleware(
+    State(state): State<Arc<AppState>>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let canonical = canonicalize_path(path);
+    if canonical.is_none() && matched_path.is_some() {
+        return next.run(request).await;
+    }
+
+    let canonical = canonical.unwrap_or_else(|| path.to_string());
+    let routes = Route::ALL
+        .iter()
+        .map(|route| (route.path(), route.props().trailing_slash));
+    let mut canonical = match trailing_slash_redirect(&canonical, routes) {
+        Some(redirect) => redirect,
+        None if canonical == path => return next.run(request).await,
+        None => canonical,
+    };
+
+    if let Some(query) = request.uri().query() {
+        canonical.push('?');
+        canonical.push_str(query);
+    }
+    match state.config.path_redirect {
+        PathRedirect::Permanent => Redirect::permanent(&canonical),
+        PathRedirect::Temporary => Redirect::temporary(&canonical),
+    }
+    .into_response()
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(canonicalize_path("/"), None);
+        assert_eq!(canonicalize_path("/item/1"), None);
+        assert_eq!(canonicalize_path("/static/"), None);
+        assert_eq!(canonicalize_path("/a%2F..%2Fb"), None);
+        assert_eq!(canonicalize_path("/a/.../b"), None);
+    }
+
+    #[test]
+    fn test_messy() {
+        let canonical = |path| canonicalize_path(path).unwrap();
+        assert_eq!(canonical("//"), "/");
+        assert_eq!(canonical("//item//1"), "/item/1");
+        assert_eq!(canonical("/./about"), "/about");
+        assert_eq!(canonical("/item/./1"), "/item/1");
+        assert_eq!(canonical("/item/2/../1"), "/item/1");
+        assert_eq!(canonical("/../../about"), "/about");
+        assert_eq!(canonical("/static/.."), "/");
+        assert_eq!(canonical("/static/."), "/static/");
+        assert_eq!(canonical("/static//"), "/static/");
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("/", "/"));
+        assert!(matches_pattern("/about", "/about"));
+        assert!(matches_pattern("/item/{id}", "/item/1"));
+        assert!(matches_pattern("/files/{*path}", "/files/a/b"));
+        assert!(!matches_pattern("/", "/about"));
+        assert!(!matches_pattern("/about", "/about/"));
+        assert!(!matches_pattern("/item/{id}", "/item/"));
+        assert!(!matches_pattern("/item/{id}", "/item/1/2"));
+        assert!(!matches_pattern("/files/{*path}", "/files/"));
+    }
+
+    const ROUTES: &[(&str, TrailingSlash)] = &[
+        ("/", TrailingSlash::Strip),
+        ("/strip", TrailingSlash::Strip),
+        ("/item/{id}", TrailingSlash::Strip),
+        ("/require/", TrailingSlash::Require),
+        ("/exact", TrailingSlash::Exact),
+        ("/exact-dir/", TrailingSlash::Exact),
+    ];
+
+    fn redirect(path: &str) -> Option<String> {
+        trailing_slash_redirect(path, ROUTES.iter().copied())
+    }
+
+    #[test]
+    fn test_trailing_slash_strip() {
+        assert_eq!(redirect("/strip/").as_deref(), Some("/strip"));
+        assert_eq!(redirect("/item/1/").as_deref(), Some("/item/1"));
+        assert_eq!(redirect("/strip"), None);
+        assert_eq!(redirect("/"), None);
+        // no route to redirect to
+        assert_eq!(redirect("/unknown/"), None);
+    }
+
+    #[test]
+    fn test_trailing_slash_require() {
+        assert_eq!(redirect("/require").as_deref(), Some("/require/"));
+        assert_eq!(redirect("/require/"), None);
+    }
+
+    #[test]
+    fn test_trailing_slash_exact() {
+        assert_eq!(redirect("/exact/"), None);
+        assert_eq!(redirect("/exact"), None);
+        assert_eq!(redirect("/exact-dir"), None);
+        assert_eq!(redirect("/exact-dir/"), None);
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::state::AppState;
+
+/// Add `Link` header with configured preload assets to HTML responses
+pub async fn preload_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+
+    let Some(preload_links) = &state.preload_links else {
+        return response;
+    };
+
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(mime::TEXT_HTML.as_ref()));
+    if is_html {
+        response
+            .headers_mut()
+            .append(header::LINK, preload_links.clone());
+    }
+
+    response
+}
@@ -0,0 +1,129 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Problem details (RFC 7807) for API clients
+//!
+//! Error responses are produced as plain text by handlers; for clients
+//! which ask for JSON these are converted into `application/problem+json`.
+//! Such responses vary on `Accept` whichever representation is chosen.
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::Serialize;
+
+use crate::vary::add_vary;
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Limit on error body size which is passed into problem details
+const MAX_DETAIL_LENGTH: usize = 4096;
+
+#[derive(Serialize)]
+struct ProblemDetails {
+    r#type: &'static str,
+    title: &'static str,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// Whether client prefers JSON over HTML
+///
+/// Browsers always accept `text/html`, while API clients
+/// ask for `application/json` (or problem details directly).
+fn wants_json(headers: &HeaderMap) -> bool {
+    let mut wants_json = false;
+    for media_type in headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|item| item.split(';').next().unwrap_or_default().trim())
+    {
+        match media_type {
+            "text/html" => return false,
+            "application/json" | PROBLEM_JSON => wants_json = true,
+            _ => {}
+        }
+    }
+    wants_json
+}
+
+fn is_plain_text(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_none_or(|value| value.starts_with(mime::TEXT_PLAIN.as_ref()))
+}
+
+pub async fn problem_details_middleware(request: Request, next: Next) -> Response {
+    let wants_json = wants_json(request.headers());
+    let mut response = next.run(request).await;
+
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) || !is_plain_text(response.headers())
+    {
+        return response;
+    }
+
+    add_vary(response.headers_mut(), header::ACCEPT);
+    if !wants_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    // server errors may carry internal details, which must not be exposed
+    let detail = if status.is_client_error() {
+        axum::body::to_bytes(body, MAX_DETAIL_LENGTH)
+            .await
+            .ok()
+            .and_then(|body| String::from_utf8(body.to_vec()).ok())
+            .filter(|detail| !detail.is_empty())
+    } else {
+        None
+    };
+
+    let problem = ProblemDetails {
+        r#type: "about:blank",
+        title: status.canonical_reason().unwrap_or_default(),
+        status: status.as_u16(),
+        detail,
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    Response::from_parts(
+        parts,
+        Body::from(serde_json::to_vec(&problem).expect("problem details should be serializable")),
+    )
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_wants_json() {
+        assert!(wants_json(&accept("application/json")));
+        assert!(wants_json(&accept("application/problem+json")));
+        assert!(wants_json(&accept("application/json; q=0.9, */*; q=0.1")));
+        assert!(!wants_json(&HeaderMap::new()));
+        assert!(!wants_json(&accept("*/*")));
+        assert!(!wants_json(&accept(
+            "text/html,application/xhtml+xml,application/json;q=0.9,*/*;q=0.8"
+        )));
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::server_timing::ServerTiming;
+use crate::state::AppState;
+
+/// Add `Server-Timing` header with durations of request phases
+///
+/// Only enabled by configuration, as it exposes internal timings.
+pub async fn server_timing_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.config.server_timing {
+        return next.run(request).await;
+    }
+
+    let timing = ServerTiming::default();
+    let mut response = timing.scope(next.run(request)).await;
+
+    if let Some(value) = timing
+        .header_value()
+        .and_then(|value| HeaderValue::try_from(value).ok())
+    {
+        response.headers_mut().insert("server-timing", value);
+    }
+
+    response
+}
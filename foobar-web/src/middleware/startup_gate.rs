@@ -0,0 +1,39 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::routes::MyRoute;
+use crate::state::AppState;
+
+const RETRY_AFTER_SECONDS: &str = "5";
+
+pub async fn startup_gate_middleware(
+    State(state): State<Arc<AppState>>,
+    route: Option<MyRoute>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_exempt = route
+        .map(|route| route.props())
+        .is_some_and(|props| props.infrastructure || props.available_during_startup);
+
+    if is_exempt || state.readiness.is_ready() {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(
+                header::RETRY_AFTER,
+                HeaderValue::from_static(RETRY_AFTER_SECONDS),
+            )],
+            "Service is starting up, please retry later",
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,205 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! OpenAPI description of JSON endpoints
+//!
+//! The document is built from [`Route::ALL`], with paths taken from
+//! [`Route::path`], so it cannot list a route which does not exist.
+//! Each route is either described here or explicitly left out (HTML
+//! pages, static files), and a new route does not compile until it's
+//! decided which. Response schemas are written by hand, and are
+//! checked against actual responses in integration tests.
+
+use std::sync::LazyLock;
+
+use serde_json::{Value, json};
+
+use crate::routes::Route;
+
+pub static OPENAPI_SPEC: LazyLock<Value> = LazyLock::new(openapi_spec);
+
+/// Description of an endpoint serving JSON
+pub struct Operation {
+    pub method: &'static str,
+    pub summary: &'static str,
+    /// Whether admin token or API key is required
+    pub admin: bool,
+    /// Response statuses with their descriptions
+    pub responses: &'static [(&'static str, &'static str)],
+    /// Schema of response body, same for all statuses
+    pub schema: fn() -> Value,
+}
+
+fn version_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["version", "git_hash", "build_time"],
+        "properties": {
+            "version": {"type": "string"},
+            "git_hash": {"type": "string"},
+            "build_time": {"type": "string"},
+        },
+    })
+}
+
+fn health_detailed_schema() -> Value {
+    let status = json!({"type": "string", "enum": ["ok", "fail"]});
+    let check = json!({
+        "type": "object",
+        "required": ["status", "latency_ms"],
+        "properties": {
+            "status": status,
+            "latency_ms": {"type": "number"},
+            "detail": {"type": "string"},
+        },
+    });
+    json!({
+        "type": "object",
+        "required": ["status", "checks"],
+        "properties": {
+            "status": status,
+            "checks": {
+                "type": "object",
+                "required": ["database", "worker_last_run"],
+                "properties": {
+                    "database": check,
+                    "worker_last_run": check,
+                },
+            },
+        },
+    })
+}
+
+fn admin_migrations_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["migrations", "up_to_date"],
+        "properties": {
+            "migrations": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["version", "description", "checksum", "state"],
+                    "properties": {
+                        "version": {"type": "integer", "format": "int64"},
+                        "description": {"type": "string"},
+                        "checksum": {"type": "string"},
+                        "state": {
+                            "type": "string",
+                            "enum": ["applied", "pending", "checksum_mismatch", "failed", "unknown"],
+                        },
+                    },
+                },
+            },
+            "up_to_date": {"type": "boolean"},
+        },
+    })
+}
+
+/// Description of the route, if it serves JSON
+pub fn operation(route: Route) -> Option<Operation> {
+    match route {
+        Route::StaticFile
+        | Route::Index
+        | Route::Item
+        | Route::About
+        | Route::Health
+        | Route::AdminFeature
+        | Route::OpenApi
+        | Route::ApiDocs => None,
+        Route::HealthDetailed => Some(Operation {
+            method: "get",
+            summary: "Status of each dependency, 503 if any check fails",
+            admin: false,
+            responses: &[("200", "All checks passed"), ("503", "Some check failed")],
+            schema: health_detailed_schema,
+        }),
+        Route::Version => Some(Operation {
+            method: "get",
+            summary: "Application version and build information",
+            admin: false,
+            responses: &[("200", "Success")],
+            schema: version_schema,
+        }),
+        Route::AdminMigrations => Some(Operation {
+            method: "get",
+            summary: "Applied and pending database migrations",
+            admin: true,
+            responses: &[("200", "Success")],
+            schema: admin_migrations_schema,
+        }),
+    }
+}
+
+fn openapi_spec() -> Value {
+    let mut paths = serde_json::Map::new();
+    for &route in Route::ALL {
+        let Some(operation) = operation(route) else {
+            continue;
+        };
+        let schema = (operation.schema)();
+        let responses: serde_json::Map<_, _> = operation
+            .responses
+            .iter()
+            .map(|(status, description)| {
+                (
+                    status.to_string(),
+                    json!({
+                        "description": description,
+                        "content": {
+                            "application/json": {"schema": schema},
+                        },
+                    }),
+                )
+            })
+            .collect();
+        let mut description = json!({
+            "summary": operation.summary,
+            "responses": responses,
+        });
+        if operation.admin {
+            description["security"] = json!([{"bearer": []}, {"api_key": []}]);
+        }
+        paths.insert(
+            route.path().to_string(),
+            json!({ operation.method: description }),
+        );
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Foobar",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearer": {"type": "http", "scheme": "bearer"},
+                "api_key": {"type": "apiKey", "in": "header", "name": "X-API-Key"},
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_match_routes() {
+        let spec = openapi_spec();
+        let paths = spec["paths"].as_object().unwrap();
+        assert_eq!(
+            paths.len(),
+            Route::ALL
+                .iter()
+                .filter(|route| operation(**route).is_some())
+                .count()
+        );
+        for path in paths.keys() {
+            assert!(Route::ALL.iter().any(|route| route.path() == path));
+        }
+    }
+}
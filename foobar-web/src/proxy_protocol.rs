@@ -0,0 +1,256 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! PROXY protocol support
+//!
+//! See <https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt>
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use axum::serve::Listener;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use tracing::warn;
+
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+const V1_PREFIX: &[u8] = b"PROXY ";
+const V1_MAX_LENGTH: usize = 107;
+
+/// Time allowed for client to send PROXY header
+const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+async fn read_v1<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    start: &[u8],
+) -> io::Result<Option<SocketAddr>> {
+    if !start.starts_with(V1_PREFIX) {
+        return Err(invalid("no PROXY header"));
+    }
+
+    // read byte by byte to not consume any data past the header
+    let mut header = start.to_vec();
+    while !header.ends_with(b"\r\n") {
+        if header.len() >= V1_MAX_LENGTH {
+            return Err(invalid("PROXY v1 header is too long"));
+        }
+        header.push(stream.read_u8().await?);
+    }
+
+    let header = std::str::from_utf8(&header[V1_PREFIX.len()..header.len() - 2])
+        .map_err(|_| invalid("PROXY v1 header is not valid UTF-8"))?;
+    let mut fields = header.split(' ');
+    match fields.next() {
+        Some("TCP4" | "TCP6") => {}
+        Some("UNKNOWN") => return Ok(None),
+        _ => return Err(invalid("unsupported PROXY v1 protocol")),
+    }
+    let (Some(src_addr), Some(_dst_addr), Some(src_port), Some(_dst_port), None) = (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    ) else {
+        return Err(invalid("malformed PROXY v1 header"));
+    };
+    let src_addr: IpAddr = src_addr
+        .parse()
+        .map_err(|_| invalid("invalid PROXY v1 source address"))?;
+    let src_port: u16 = src_port
+        .parse()
+        .map_err(|_| invalid("invalid PROXY v1 source port"))?;
+    Ok(Some(SocketAddr::new(src_addr, src_port)))
+}
+
+async fn read_v2<R: AsyncRead + Unpin>(stream: &mut R) -> io::Result<Option<SocketAddr>> {
+    let version_command = stream.read_u8().await?;
+    let family = stream.read_u8().await?;
+    let length = stream.read_u16().await?;
+    let mut payload = vec![0; length as usize];
+    stream.read_exact(&mut payload).await?;
+
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported PROXY protocol version"));
+    }
+    match version_command & 0x0f {
+        0x0 => return Ok(None), // LOCAL, e.g. health check from the proxy itself
+        0x1 => {}               // PROXY
+        _ => return Err(invalid("unsupported PROXY v2 command")),
+    }
+
+    match family >> 4 {
+        0x1 if payload.len() >= 12 => {
+            let addr: [u8; 4] = payload[0..4].try_into().unwrap();
+            let port = u16::from_be_bytes([payload[8], payload[9]]);
+            Ok(Some(SocketAddr::new(Ipv4Addr::from(addr).into(), port)))
+        }
+        0x2 if payload.len() >= 36 => {
+            let addr: [u8; 16] = payload[0..16].try_into().unwrap();
+            let port = u16::from_be_bytes([payload[32], payload[33]]);
+            Ok(Some(SocketAddr::new(Ipv6Addr::from(addr).into(), port)))
+        }
+        0x1 | 0x2 => Err(invalid("truncated PROXY v2 addresses")),
+        _ => Ok(None), // AF_UNSPEC or AF_UNIX
+    }
+}
+
+/// Read PROXY protocol (v1 or v2) header from the stream
+///
+/// Returns client address conveyed by the proxy, or `None` if the
+/// proxy did not provide one (e.g. for its own health checks).
+pub async fn read_proxy_header<R: AsyncRead + Unpin>(
+    stream: &mut R,
+) -> io::Result<Option<SocketAddr>> {
+    // both v2 signature and shortest possible v1 header are at least 12 bytes long
+    let mut start = [0; V2_SIGNATURE.len()];
+    stream.read_exact(&mut start).await?;
+    if &start == V2_SIGNATURE {
+        read_v2(stream).await
+    } else {
+        read_v1(stream, &start).await
+    }
+}
+
+async fn accept_proxied(
+    mut stream: TcpStream,
+    peer_addr: SocketAddr,
+) -> io::Result<(TcpStream, SocketAddr)> {
+    let addr = tokio::time::timeout(HEADER_TIMEOUT, read_proxy_header(&mut stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "no PROXY header received"))??;
+    Ok((stream, addr.unwrap_or(peer_addr)))
+}
+
+/// TCP listener which expects PROXY protocol header on each connection
+///
+/// Client address for the connection is taken from the header.
+/// Headers are read concurrently, so slow clients do not block
+/// accepting other connections; connections with missing or
+/// malformed header are dropped.
+pub struct ProxyProtocolListener {
+    inner: TcpListener,
+    pending: JoinSet<io::Result<(TcpStream, SocketAddr)>>,
+}
+
+impl ProxyProtocolListener {
+    pub fn new(inner: TcpListener) -> Self {
+        Self {
+            inner,
+            pending: JoinSet::new(),
+        }
+    }
+}
+
+impl Listener for ProxyProtocolListener {
+    type Io = TcpStream;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            tokio::select! {
+                (stream, peer_addr) = Listener::accept(&mut self.inner) => {
+                    self.pending.spawn(accept_proxied(stream, peer_addr));
+                }
+                Some(res) = self.pending.join_next() => match res {
+                    Ok(Ok(accepted)) => return accepted,
+                    Ok(Err(error)) => warn!(%error, "dropping connection with bad PROXY header"),
+                    Err(error) => warn!(%error, "PROXY header reading task failed"),
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+
+    async fn parse(mut data: &[u8]) -> (io::Result<Option<SocketAddr>>, &[u8]) {
+        let res = read_proxy_header(&mut data).await;
+        (res, data)
+    }
+
+    #[tokio::test]
+    async fn test_v1() {
+        let (res, rest) = parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\nGET /").await;
+        assert_eq!(res.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"GET /");
+
+        let (res, rest) = parse(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\nGET /").await;
+        assert_eq!(res.unwrap(), Some("[2001:db8::1]:56324".parse().unwrap()));
+        assert_eq!(rest, b"GET /");
+
+        let (res, rest) = parse(b"PROXY UNKNOWN\r\nGET /").await;
+        assert_eq!(res.unwrap(), None);
+        assert_eq!(rest, b"GET /");
+    }
+
+    #[tokio::test]
+    async fn test_v1_invalid() {
+        assert!(parse(b"GET / HTTP/1.1\r\n\r\n").await.0.is_err());
+        assert!(parse(b"PROXY TCP4 192.0.2.1\r\n").await.0.is_err());
+        assert!(
+            parse(b"PROXY TCP4 example.com 198.51.100.1 56324 443\r\n")
+                .await
+                .0
+                .is_err()
+        );
+        assert!(parse(&[b'P'; 200]).await.0.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_v2() {
+        let mut data = V2_SIGNATURE.to_vec();
+        data.extend([0x21, 0x11, 0, 12]);
+        data.extend([192, 0, 2, 1, 198, 51, 100, 1, 0xdc, 0x04, 0x01, 0xbb]);
+        data.extend(b"GET /");
+        let (res, rest) = parse(&data).await;
+        assert_eq!(res.unwrap(), Some("192.0.2.1:56324".parse().unwrap()));
+        assert_eq!(rest, b"GET /");
+    }
+
+    #[tokio::test]
+    async fn test_v2_local() {
+        let mut data = V2_SIGNATURE.to_vec();
+        data.extend([0x20, 0x00, 0, 0]);
+        data.extend(b"GET /");
+        let (res, rest) = parse(&data).await;
+        assert_eq!(res.unwrap(), None);
+        assert_eq!(rest, b"GET /");
+    }
+
+    #[tokio::test]
+    async fn test_listener() {
+        let mut listener =
+            ProxyProtocolListener::new(TcpListener::bind("127.0.0.1:0").await.unwrap());
+        let local_addr = listener.local_addr().unwrap();
+
+        // client which never sends the header must not block others
+        let _silent = TcpStream::connect(local_addr).await.unwrap();
+
+        let mut client = TcpStream::connect(local_addr).await.unwrap();
+        client
+            .write_all(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n")
+            .await
+            .unwrap();
+
+        let (_, addr) = tokio::time::timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .unwrap();
+        assert_eq!(addr, "192.0.2.1:56324".parse().unwrap());
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::extract::{FromRequestParts, Query};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+
+use crate::state::AppState;
+
+/// Default maximal length of query string
+pub const DEFAULT_MAX_QUERY_LENGTH: usize = 2048;
+
+/// Default maximal length of a single query parameter
+pub const DEFAULT_MAX_QUERY_PARAM_LENGTH: usize = 256;
+
+/// Query extractor which rejects oversized query strings
+///
+/// Same as [`Query`], but responds with 400 if either the whole query
+/// string or any single parameter exceeds configured length, before
+/// parsing it, and so before any database work is done by a handler.
+/// Parameters are measured in their raw, percent encoded form.
+pub struct LimitedQuery<T>(pub T);
+
+impl<T> FromRequestParts<Arc<AppState>> for LimitedQuery<T>
+where
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let max_length = state
+            .config
+            .max_query_length
+            .unwrap_or(DEFAULT_MAX_QUERY_LENGTH);
+        let max_param_length = state
+            .config
+            .max_query_param_length
+            .unwrap_or(DEFAULT_MAX_QUERY_PARAM_LENGTH);
+
+        if query.len() > max_length {
+            return Err((StatusCode::BAD_REQUEST, "Query string too long").into_response());
+        }
+        if query.split('&').any(|param| param.len() > max_param_length) {
+            return Err((StatusCode::BAD_REQUEST, "Query parameter too long").into_response());
+        }
+
+        Query::try_from_uri(&parts.uri)
+            .map(|Query(value)| LimitedQuery(value))
+            .map_err(IntoResponse::into_response)
+    }
+}
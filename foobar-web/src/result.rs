@@ -1,14 +1,41 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use axum::response::{IntoResponse, Response};
-use tracing::error;
+use metrics::counter;
+use tracing::{error, warn};
+
+/// Value of `Retry-After` header when database is overloaded, in seconds
+const OVERLOAD_RETRY_AFTER: &str = "5";
 
 pub struct HandlerError(anyhow::Error);
 
+impl HandlerError {
+    /// Whether the error is caused by timeout acquiring a database connection
+    ///
+    /// This is a transient capacity issue rather than a bug, so it's
+    /// reported to clients as 503 Service Unavailable.
+    fn is_acquire_timeout(&self) -> bool {
+        self.0.chain().any(|cause| {
+            matches!(
+                cause.downcast_ref::<sqlx::Error>(),
+                Some(sqlx::Error::PoolTimedOut)
+            )
+        })
+    }
+}
+
 impl IntoResponse for HandlerError {
     fn into_response(self) -> Response {
+        if self.is_acquire_timeout() {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, OVERLOAD_RETRY_AFTER)],
+                "Service temporarily overloaded, please retry later",
+            )
+                .into_response();
+        }
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             if cfg!(debug_assertions) {
@@ -26,9 +53,14 @@ where
     E: Into<anyhow::Error>,
 {
     fn from(err: E) -> Self {
-        let err = err.into();
-        error!("{:#?}", err);
-        Self(err)
+        let err = Self(err.into());
+        if err.is_acquire_timeout() {
+            warn!("timed out acquiring database connection");
+            counter!("foobar_db_acquire_timeouts_total").increment(1);
+        } else {
+            error!("{:#?}", err.0);
+        }
+        err
     }
 }
 
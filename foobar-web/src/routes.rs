@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum_myroutes::routes;
 
@@ -16,12 +17,61 @@ pub enum Section {
     Docs,
 }
 
+/// How requests differing from the route path in trailing slash are handled
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// Path with trailing slash is redirected to the route path without it
+    #[default]
+    Strip,
+    /// Path without trailing slash is redirected to the route path which
+    /// has it, e.g. for directory-like resources
+    Require,
+    /// No redirects, only the exact route path is served, e.g. for
+    /// file-like resources fetched by tools by their exact URL
+    Exact,
+}
+
+/// Announcement of route removal, see RFC 9745 and RFC 8594
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deprecation {
+    /// When the route was deprecated, as unix timestamp
+    pub since: i64,
+    /// When the route is going to be removed, as unix timestamp
+    pub sunset: Option<i64>,
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct RouteProps {
     pub section: Section,
+    // Page title, combined with site name in HTML title. Handlers of
+    // parameterized routes may override it in template
+    pub title: Option<&'static str>,
     // XXX: Set on routes which produce embeddable resources, such as badge images
     // Affects headers middleware
     pub allow_embedding: bool,
+    // Set on service routes (such as health check) which must be available
+    // regardless of application state. Affects startup gate and
+    // concurrency limit middlewares
+    pub infrastructure: bool,
+    // Set on routes which must be available before startup completes,
+    // but are not exempt from concurrency limit (such as migration
+    // status checked during deploys). Affects startup gate middleware
+    pub available_during_startup: bool,
+    // Set on routes which require valid JWT bearer token. Affects JWT auth
+    // middleware
+    pub jwt_required: bool,
+    // Value of Cache-Control header for successful responses, unless
+    // set by the handler. Affects headers middleware
+    pub cache_control: Option<&'static str>,
+    // Time limit for handler which is mostly spent in database queries;
+    // on expiry, 504 is returned. Affects database timeout middleware
+    pub db_timeout: Option<Duration>,
+    // Redirect policy for paths differing from the route path in
+    // trailing slash. Affects path normalization middleware
+    pub trailing_slash: TrailingSlash,
+    // Set on legacy routes which are going to be removed, so clients
+    // are warned to migrate. Affects headers middleware
+    pub deprecation: Option<Deprecation>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -29,10 +79,69 @@ pub struct RouteProps {
 pub enum Route {
     #[get("/static/{file_name}", handler = handlers::static_file)]
     StaticFile,
-    #[get("/", handler = handlers::index, props = RouteProps { section: Section::Items, ..Default::default() })]
+    #[get("/", handler = handlers::index, props = RouteProps { section: Section::Items, title: Some("Items"), cache_control: Some("no-cache"), db_timeout: Some(Duration::from_secs(2)), ..Default::default() })]
     Index,
-    #[get("/item/{id}", handler = handlers::item, props = RouteProps { section: Section::Items, ..Default::default() })]
+    #[get("/item/{id}", handler = handlers::item, props = RouteProps { section: Section::Items, title: Some("Item"), cache_control: Some("no-cache"), db_timeout: Some(Duration::from_secs(2)), ..Default::default() })]
     Item,
-    #[get("/about", handler = handlers::about, props = RouteProps { section: Section::Docs, ..Default::default() })]
+    #[get("/about", handler = handlers::about, props = RouteProps { section: Section::Docs, title: Some("About"), cache_control: Some("no-cache"), ..Default::default() })]
     About,
+    #[get("/health", handler = handlers::health, props = RouteProps { infrastructure: true, ..Default::default() })]
+    Health,
+    #[get("/health/detailed", handler = handlers::health_detailed, props = RouteProps { infrastructure: true, ..Default::default() })]
+    HealthDetailed,
+    #[get("/version", handler = handlers::version, props = RouteProps { infrastructure: true, ..Default::default() })]
+    Version,
+    #[get("/admin/migrations", handler = handlers::admin_migrations, props = RouteProps { available_during_startup: true, ..Default::default() })]
+    AdminMigrations,
+    #[put("/admin/features/{name}", handler = handlers::admin_feature)]
+    AdminFeature,
+    #[get("/openapi.json", handler = handlers::openapi, props = RouteProps { trailing_slash: TrailingSlash::Exact, ..Default::default() })]
+    OpenApi,
+    #[get("/api/docs", handler = handlers::api_docs, props = RouteProps { section: Section::Docs, title: Some("API"), cache_control: Some("no-cache"), ..Default::default() })]
+    ApiDocs,
+}
+
+impl Route {
+    /// All routes, used to find a route for a path before routing
+    pub const ALL: &[Route] = &[
+        Route::StaticFile,
+        Route::Index,
+        Route::Item,
+        Route::About,
+        Route::Health,
+        Route::HealthDetailed,
+        Route::Version,
+        Route::AdminMigrations,
+        Route::AdminFeature,
+        Route::OpenApi,
+        Route::ApiDocs,
+    ];
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_routes_listed() {
+        // exhaustive match makes adding a route without listing it a compile error here
+        let position = |route: Route| match route {
+            Route::StaticFile => 0,
+            Route::Index => 1,
+            Route::Item => 2,
+            Route::About => 3,
+            Route::Health => 4,
+            Route::HealthDetailed => 5,
+            Route::Version => 6,
+            Route::AdminMigrations => 7,
+            Route::AdminFeature => 8,
+            Route::OpenApi => 9,
+            Route::ApiDocs => 10,
+        };
+        for (i, route) in Route::ALL.iter().enumerate() {
+            assert_eq!(position(*route), i);
+        }
+        assert_eq!(Route::ALL.len(), position(Route::ApiDocs) + 1);
+    }
 }
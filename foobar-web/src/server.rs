@@ -0,0 +1,747 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::Infallible;
+use std::fmt;
+use std::io::IoSlice;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::pin::{Pin, pin};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{self, Poll};
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::Router;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::serve::Listener;
+use hyper::body::Incoming;
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto::Builder;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::{TcpListener, UnixListener, UnixStream};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+use tracing::{debug, error, warn};
+
+use crate::middleware::{RequestTimedOut, record_rejected_request};
+use crate::proxy_protocol::ProxyProtocolListener;
+use crate::tls::{ReloadableCertResolver, TlsConfig, TlsListener, reload_on_sighup};
+
+const UNIX_PREFIX: &str = "unix:";
+
+/// Response sent when client does not send request headers in time
+const HEADER_TIMEOUT_RESPONSE: &[u8] =
+    b"HTTP/1.1 408 Request Timeout\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+
+/// Time allowed for sending response to a rejected request
+const REJECTION_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Address to listen on, either TCP `host:port` or `unix:/path/to.sock`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl FromStr for ListenAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix(UNIX_PREFIX) {
+            if path.is_empty() {
+                return Err("empty unix socket path".to_string());
+            }
+            Ok(Self::Unix(path.into()))
+        } else {
+            s.parse()
+                .map(Self::Tcp)
+                .map_err(|err| format!("invalid socket address: {err}"))
+        }
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "{UNIX_PREFIX}{}", path.display()),
+        }
+    }
+}
+
+impl TryFrom<String> for ListenAddr {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ListenAddr> for String {
+    fn from(addr: ListenAddr) -> Self {
+        addr.to_string()
+    }
+}
+
+/// Settings for serving the application
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub listen: ListenAddr,
+    /// Expect PROXY protocol header on each connection
+    pub proxy_protocol: bool,
+    /// Serve HTTPS with given certificate instead of plain HTTP
+    pub tls: Option<TlsConfig>,
+    /// Accept HTTP/2 connections (h2c for plain HTTP, or negotiated
+    /// through ALPN for HTTPS) in addition to HTTP/1
+    pub http2: bool,
+    pub timeouts: ServerTimeouts,
+}
+
+/// Connection level timeouts
+#[derive(Clone, Debug)]
+pub struct ServerTimeouts {
+    /// Time allowed for client to send complete request headers
+    ///
+    /// Only applies to HTTP/1. Client which has started sending a
+    /// request and did not finish its headers in time is answered
+    /// with 408 Request Timeout. For keep-alive connections, this
+    /// also limits the wait for the next request, in which case the
+    /// connection is just closed.
+    pub header_read: Duration,
+    /// Time after which a connection without requests in flight is closed
+    pub keep_alive: Duration,
+    /// Time allowed for handling a request
+    ///
+    /// Request which takes longer is answered with 503 Service
+    /// Unavailable, as the client is not at fault.
+    pub request: Duration,
+    /// Time allowed for in-flight requests to finish on shutdown
+    ///
+    /// Connections still open after that are forcibly closed.
+    pub shutdown: Duration,
+}
+
+impl Default for ServerTimeouts {
+    fn default() -> Self {
+        Self {
+            header_read: Duration::from_secs(30),
+            keep_alive: Duration::from_secs(60),
+            request: Duration::from_secs(60),
+            shutdown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Client address for TCP connections
+///
+/// Available to handlers through `ConnectInfo<ClientAddr>`. When
+/// PROXY protocol is enabled, this is the address conveyed by the
+/// proxy.
+#[derive(Clone, Copy, Debug)]
+pub struct ClientAddr(pub SocketAddr);
+
+/// Listener address which may be exposed to handlers
+trait PeerAddr {
+    fn client_addr(&self) -> Option<ClientAddr>;
+}
+
+impl PeerAddr for SocketAddr {
+    fn client_addr(&self) -> Option<ClientAddr> {
+        Some(ClientAddr(*self))
+    }
+}
+
+impl PeerAddr for tokio::net::unix::SocketAddr {
+    fn client_addr(&self) -> Option<ClientAddr> {
+        None
+    }
+}
+
+/// Removes unix socket file when dropped
+struct SocketFileGuard(PathBuf);
+
+impl Drop for SocketFileGuard {
+    fn drop(&mut self) {
+        if let Err(error) = std::fs::remove_file(&self.0) {
+            error!(%error, path = %self.0.display(), "cannot remove socket file");
+        }
+    }
+}
+
+async fn bind_unix(path: &Path) -> anyhow::Result<UnixListener> {
+    match UnixListener::bind(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => {
+            // socket file may be left by the process which was not shut down
+            // cleanly; it's safe to replace it if nobody is listening on it
+            if UnixStream::connect(path).await.is_ok() {
+                return Err(err).context("socket is in use by another process");
+            }
+            warn!(path = %path.display(), "removing stale socket file");
+            std::fs::remove_file(path).context("cannot remove stale socket file")?;
+            Ok(UnixListener::bind(path)?)
+        }
+        res => Ok(res?),
+    }
+}
+
+fn connection_builder(config: &ServerConfig) -> Builder<TokioExecutor> {
+    let mut builder = Builder::new(TokioExecutor::new());
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(config.timeouts.header_read);
+    if config.http2 {
+        builder
+    } else {
+        builder.http1_only()
+    }
+}
+
+/// Tracks requests on a connection to detect when it becomes idle
+///
+/// Hyper only has ping based keep-alive for HTTP/2, which detects dead
+/// peers but never closes live idle ones, so idle timeout is done here
+/// for both protocols.
+#[derive(Default)]
+struct ConnectionActivity {
+    active_requests: AtomicUsize,
+    last_activity: Mutex<Option<Instant>>,
+}
+
+impl ConnectionActivity {
+    fn start_request(self: &Arc<Self>) -> RequestGuard {
+        self.active_requests.fetch_add(1, Ordering::Relaxed);
+        RequestGuard(self.clone())
+    }
+
+    /// Time when the connection became idle, `None` if requests are in flight
+    fn idle_since(&self, connected_at: Instant) -> Option<Instant> {
+        if self.active_requests.load(Ordering::Relaxed) > 0 {
+            return None;
+        }
+        let last_activity = *self
+            .last_activity
+            .lock()
+            .expect("activity lock should not be poisoned");
+        Some(last_activity.unwrap_or(connected_at))
+    }
+}
+
+/// Marks the request as finished when dropped
+struct RequestGuard(Arc<ConnectionActivity>);
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        *self
+            .0
+            .last_activity
+            .lock()
+            .expect("activity lock should not be poisoned") = Some(Instant::now());
+        self.0.active_requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Connection stream shared between hyper and the server
+///
+/// This allows the server to respond to the client after hyper has
+/// given up on the connection. Hyper closes the connection without
+/// a response on header read timeout, while we want to send 408 to
+/// clients which have started sending a request.
+struct SharedIo<Io> {
+    io: Arc<Mutex<Io>>,
+    /// Whether data was received since the last write, that is,
+    /// the client is in the middle of sending a request
+    receiving: Arc<AtomicBool>,
+}
+
+impl<Io> Clone for SharedIo<Io> {
+    fn clone(&self) -> Self {
+        Self {
+            io: self.io.clone(),
+            receiving: self.receiving.clone(),
+        }
+    }
+}
+
+impl<Io: Unpin> SharedIo<Io> {
+    fn new(io: Io) -> Self {
+        Self {
+            io: Arc::new(Mutex::new(io)),
+            receiving: Default::default(),
+        }
+    }
+
+    fn is_receiving(&self) -> bool {
+        self.receiving.load(Ordering::Relaxed)
+    }
+
+    fn poll_io<T>(&self, f: impl FnOnce(Pin<&mut Io>) -> T) -> T {
+        let mut io = self.io.lock().expect("io lock should not be poisoned");
+        f(Pin::new(&mut *io))
+    }
+}
+
+impl<Io: AsyncRead + Unpin> AsyncRead for SharedIo<Io> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        let res = self.poll_io(|io| io.poll_read(cx, buf));
+        if buf.filled().len() > filled {
+            self.receiving.store(true, Ordering::Relaxed);
+        }
+        res
+    }
+}
+
+impl<Io: AsyncWrite + Unpin> AsyncWrite for SharedIo<Io> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let res = self.poll_io(|io| io.poll_write(cx, buf));
+        if matches!(res, Poll::Ready(Ok(written)) if written > 0) {
+            self.receiving.store(false, Ordering::Relaxed);
+        }
+        res
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        let res = self.poll_io(|io| io.poll_write_vectored(cx, bufs));
+        if matches!(res, Poll::Ready(Ok(written)) if written > 0) {
+            self.receiving.store(false, Ordering::Relaxed);
+        }
+        res
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io
+            .lock()
+            .expect("io lock should not be poisoned")
+            .is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_io(|io| io.poll_flush(cx))
+    }
+
+    fn poll_shutdown(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.poll_io(|io| io.poll_shutdown(cx))
+    }
+}
+
+/// Status of the response for request rejected by hyper itself
+///
+/// Hyper answers malformed and oversized requests on its own, so
+/// these only need to be accounted. Overlong URIs are answered with
+/// 414 and are indistinguishable from oversized headers here, so are
+/// counted as 431 too. Header read timeout is not answered by hyper.
+fn rejection_status(error: &hyper::Error) -> Option<StatusCode> {
+    if error.is_timeout() {
+        Some(StatusCode::REQUEST_TIMEOUT)
+    } else if error.is_parse_too_large() {
+        Some(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)
+    } else if error.is_parse() {
+        Some(StatusCode::BAD_REQUEST)
+    } else {
+        None
+    }
+}
+
+/// Respond to and account request rejected by hyper
+async fn handle_rejection<Io>(error: &hyper::Error, io: &mut SharedIo<Io>)
+where
+    Io: AsyncWrite + Unpin,
+{
+    let Some(status) = rejection_status(error) else {
+        return;
+    };
+    if status == StatusCode::REQUEST_TIMEOUT {
+        // idle keep-alive connections are closed silently
+        if !io.is_receiving() {
+            return;
+        }
+        let write = async {
+            io.write_all(HEADER_TIMEOUT_RESPONSE).await?;
+            io.shutdown().await
+        };
+        if let Err(error) = tokio::time::timeout(REJECTION_WRITE_TIMEOUT, write)
+            .await
+            .unwrap_or_else(|_| Err(std::io::ErrorKind::TimedOut.into()))
+        {
+            debug!(%error, "failed to send request timeout response");
+        }
+    }
+    debug!(%status, %error, "request rejected");
+    record_rejected_request(status);
+}
+
+async fn handle_request(
+    app: Router,
+    mut request: Request<Incoming>,
+    client_addr: Option<ClientAddr>,
+    request_timeout: Duration,
+    activity: Arc<ConnectionActivity>,
+) -> Result<Response, Infallible> {
+    let _guard = activity.start_request();
+    if let Some(client_addr) = client_addr {
+        request.extensions_mut().insert(ConnectInfo(client_addr));
+    }
+    let timed_out = RequestTimedOut::default();
+    request.extensions_mut().insert(timed_out.clone());
+
+    // request future is dropped only after timeout is flagged
+    let mut response = pin!(app.oneshot(request));
+    match tokio::time::timeout(request_timeout, response.as_mut()).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!("request timed out");
+            timed_out.set();
+            Ok(StatusCode::SERVICE_UNAVAILABLE.into_response())
+        }
+    }
+}
+
+/// Serve single connection until it's closed
+///
+/// Connection is gracefully closed when `shutdown` is signalled or
+/// when it stays idle for longer than keep-alive timeout.
+async fn serve_connection<Io>(
+    builder: Builder<TokioExecutor>,
+    io: Io,
+    client_addr: Option<ClientAddr>,
+    app: Router,
+    timeouts: ServerTimeouts,
+    mut shutdown: watch::Receiver<()>,
+) where
+    Io: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let activity = Arc::new(ConnectionActivity::default());
+    let service = service_fn({
+        let activity = activity.clone();
+        move |request| {
+            handle_request(
+                app.clone(),
+                request,
+                client_addr,
+                timeouts.request,
+                activity.clone(),
+            )
+        }
+    });
+    let mut io = SharedIo::new(io);
+    let mut connection =
+        pin!(builder.serve_connection_with_upgrades(TokioIo::new(io.clone()), service));
+
+    let connected_at = Instant::now();
+    let idle_check = tokio::time::sleep(timeouts.keep_alive);
+    let mut idle_check = pin!(idle_check);
+    let mut closing = false;
+
+    loop {
+        tokio::select! {
+            res = connection.as_mut() => {
+                if let Err(error) = res {
+                    debug!(error, "failed to serve connection");
+                    if let Some(error) = error.downcast_ref::<hyper::Error>() {
+                        handle_rejection(error, &mut io).await;
+                    }
+                }
+                break;
+            }
+            _ = shutdown.changed(), if !closing => {
+                connection.as_mut().graceful_shutdown();
+                closing = true;
+            }
+            _ = &mut idle_check, if !closing => {
+                match activity.idle_since(connected_at) {
+                    Some(idle_since) if idle_since.elapsed() >= timeouts.keep_alive => {
+                        debug!("closing idle connection");
+                        connection.as_mut().graceful_shutdown();
+                        closing = true;
+                    }
+                    Some(idle_since) => idle_check.as_mut().reset(idle_since + timeouts.keep_alive),
+                    None => idle_check.as_mut().reset(Instant::now() + timeouts.keep_alive),
+                }
+            }
+        }
+    }
+}
+
+/// Accept connections and serve them until `shutdown` completes
+///
+/// After that, stops accepting and waits for in-flight requests to
+/// finish, closing connections which are not done within shutdown
+/// timeout.
+async fn serve_connections<L>(
+    mut listener: L,
+    config: &ServerConfig,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+) where
+    L: Listener,
+    L::Addr: PeerAddr,
+{
+    let builder = connection_builder(config);
+    let (shutdown_tx, shutdown_rx) = watch::channel(());
+    let mut connections = JoinSet::new();
+    let mut shutdown = pin!(shutdown);
+
+    loop {
+        let (io, addr) = tokio::select! {
+            accepted = listener.accept() => accepted,
+            _ = &mut shutdown => break,
+        };
+
+        connections.spawn(serve_connection(
+            builder.clone(),
+            io,
+            addr.client_addr(),
+            app.clone(),
+            config.timeouts.clone(),
+            shutdown_rx.clone(),
+        ));
+
+        // reap finished connections
+        while connections.try_join_next().is_some() {}
+    }
+
+    drop(listener);
+    let _ = shutdown_tx.send(());
+    let drain = async { while connections.join_next().await.is_some() {} };
+    if tokio::time::timeout(config.timeouts.shutdown, drain)
+        .await
+        .is_err()
+    {
+        warn!(
+            connections = connections.len(),
+            "shutdown timeout expired, closing remaining connections"
+        );
+        connections.shutdown().await;
+    }
+}
+
+async fn serve_listener<L>(
+    listener: L,
+    acceptor: Option<TlsAcceptor>,
+    config: &ServerConfig,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+) where
+    L: Listener,
+    L::Addr: PeerAddr + 'static,
+{
+    match acceptor {
+        Some(acceptor) => {
+            let listener = TlsListener::new(listener, acceptor);
+            serve_connections(listener, config, app, shutdown).await;
+        }
+        None => serve_connections(listener, config, app, shutdown).await,
+    }
+}
+
+/// Serve the application until `shutdown` completes
+///
+/// For unix sockets, socket file is removed after the server stops.
+/// When TLS is enabled, certificate is reloaded on SIGHUP.
+pub async fn serve(
+    config: &ServerConfig,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    // tasks are aborted when the set is dropped
+    let mut background_tasks = JoinSet::new();
+
+    let acceptor = if let Some(tls) = &config.tls {
+        let resolver = Arc::new(ReloadableCertResolver::new(tls.clone())?);
+        background_tasks.spawn(reload_on_sighup(resolver.clone()));
+        let alpn_protocols: &[&[u8]] = if config.http2 {
+            &[b"h2", b"http/1.1"]
+        } else {
+            &[b"http/1.1"]
+        };
+        Some(resolver.acceptor(alpn_protocols)?)
+    } else {
+        None
+    };
+
+    match &config.listen {
+        ListenAddr::Tcp(addr) => {
+            let listener = TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("cannot listen on {addr}"))?;
+            if config.proxy_protocol {
+                let listener = ProxyProtocolListener::new(listener);
+                serve_listener(listener, acceptor, config, app, shutdown).await;
+            } else {
+                serve_listener(listener, acceptor, config, app, shutdown).await;
+            }
+        }
+        ListenAddr::Unix(_) if config.proxy_protocol => {
+            anyhow::bail!("PROXY protocol is only supported when listening on TCP socket");
+        }
+        ListenAddr::Unix(path) => {
+            let listener = bind_unix(path)
+                .await
+                .with_context(|| format!("cannot listen on {}", path.display()))?;
+            let _guard = SocketFileGuard(path.clone());
+            serve_listener(listener, acceptor, config, app, shutdown).await;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use axum::routing::get;
+    use metrics_util::debugging::DebuggingRecorder;
+    use tokio::io::AsyncReadExt;
+
+    use foobar_common::testing::counter_values_by_label;
+
+    use super::*;
+
+    /// Send raw `request` over a single connection, returning the raw
+    /// response and statuses of requests recorded in metrics
+    fn exchange(request: &'static [u8], timeouts: ServerTimeouts) -> (String, Vec<String>) {
+        let recorder = DebuggingRecorder::new();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let response = metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let config = ServerConfig {
+                    listen: "127.0.0.1:0".parse().unwrap(),
+                    proxy_protocol: false,
+                    tls: None,
+                    http2: false,
+                    timeouts,
+                };
+                let app = Router::new()
+                    .route("/", get(|| async { "ok" }))
+                    .route("/slow", get(std::future::pending::<&str>));
+                let (mut client, server) = tokio::io::duplex(4096);
+                let (_shutdown_tx, shutdown_rx) = watch::channel(());
+                let connection = tokio::spawn(serve_connection(
+                    connection_builder(&config),
+                    server,
+                    None,
+                    app,
+                    config.timeouts.clone(),
+                    shutdown_rx,
+                ));
+
+                client.write_all(request).await.unwrap();
+                let mut response = vec![];
+                client.read_to_end(&mut response).await.unwrap();
+                connection.await.unwrap();
+                String::from_utf8(response).unwrap()
+            })
+        });
+
+        let statuses =
+            counter_values_by_label(&recorder, "foobar_web_http_requests_total", "status")
+                .into_iter()
+                .flat_map(|(status, count)| std::iter::repeat_n(status, count as usize))
+                .collect();
+
+        (response, statuses)
+    }
+
+    #[test]
+    fn test_header_timeout() {
+        let (response, statuses) = exchange(
+            b"GET / HTTP/1.1\r\nHost: local",
+            ServerTimeouts {
+                header_read: Duration::from_millis(100),
+                ..Default::default()
+            },
+        );
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+        assert_eq!(statuses, vec!["408"]);
+    }
+
+    #[test]
+    fn test_idle_keepalive_header_timeout() {
+        // waiting for the next request times out, but it's not answered
+        let (response, statuses) = exchange(
+            b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            ServerTimeouts {
+                header_read: Duration::from_millis(100),
+                ..Default::default()
+            },
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(!response.contains("408"));
+        assert!(statuses.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_request() {
+        let (response, statuses) = exchange(b"GARBAGE\r\n\r\n", Default::default());
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+        assert_eq!(statuses, vec!["400"]);
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        // slow handler is server's fault, so it's not answered with 408
+        let (response, _) = exchange(
+            b"GET /slow HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            ServerTimeouts {
+                request: Duration::from_millis(100),
+                ..Default::default()
+            },
+        );
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+    }
+
+    #[test]
+    fn test_parse_listen_addr() {
+        assert_eq!(
+            "127.0.0.1:3000".parse(),
+            Ok(ListenAddr::Tcp("127.0.0.1:3000".parse().unwrap()))
+        );
+        assert_eq!(
+            "[::1]:3000".parse(),
+            Ok(ListenAddr::Tcp("[::1]:3000".parse().unwrap()))
+        );
+        assert_eq!(
+            "unix:/run/foobar.sock".parse(),
+            Ok(ListenAddr::Unix("/run/foobar.sock".into()))
+        );
+        assert!("unix:".parse::<ListenAddr>().is_err());
+        assert!("localhost".parse::<ListenAddr>().is_err());
+    }
+
+    #[test]
+    fn test_display_listen_addr() {
+        for s in ["127.0.0.1:3000", "[::1]:3000", "unix:/run/foobar.sock"] {
+            assert_eq!(s.parse::<ListenAddr>().unwrap().to_string(), s);
+        }
+    }
+}
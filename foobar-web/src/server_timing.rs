@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Collection of request phase timings for `Server-Timing` header
+//!
+//! Timings are accumulated in a task local, which is only set up by
+//! [`crate::middleware::server_timing_middleware`] when the header is
+//! enabled, otherwise recording is a no-op.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static SERVER_TIMING: ServerTiming;
+}
+
+/// Durations of request phases, accumulated over all occurrences
+#[derive(Clone, Default)]
+pub struct ServerTiming(Arc<Mutex<Vec<(&'static str, Duration)>>>);
+
+impl ServerTiming {
+    /// Run future with phase timings collected into this instance
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        SERVER_TIMING.scope(self.clone(), future).await
+    }
+
+    fn add(&self, name: &'static str, duration: Duration) {
+        let mut phases = self.0.lock().expect("timing lock should not be poisoned");
+        match phases.iter_mut().find(|(phase, _)| *phase == name) {
+            Some((_, total)) => *total += duration,
+            None => phases.push((name, duration)),
+        }
+    }
+
+    /// Format collected timings as `Server-Timing` header value
+    ///
+    /// Returns `None` if nothing was recorded.
+    pub fn header_value(&self) -> Option<String> {
+        let phases = self.0.lock().expect("timing lock should not be poisoned");
+        (!phases.is_empty()).then(|| {
+            phases
+                .iter()
+                .map(|(name, duration)| {
+                    format!("{name};dur={:.3}", duration.as_secs_f64() * 1000.0)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+}
+
+/// Record duration of request phase, if timings are being collected
+pub fn record(name: &'static str, duration: Duration) {
+    let _ = SERVER_TIMING.try_with(|timing| timing.add(name, duration));
+}
+
+/// Run future, recording its duration as request phase
+pub async fn measure<F: Future>(name: &'static str, future: F) -> F::Output {
+    let started = Instant::now();
+    let output = future.await;
+    record(name, started.elapsed());
+    output
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_phases_accumulated() {
+        let timing = ServerTiming::default();
+        timing
+            .scope(async {
+                record("db", Duration::from_millis(1));
+                record("render", Duration::from_micros(500));
+                record("db", Duration::from_millis(2));
+            })
+            .await;
+        assert_eq!(
+            timing.header_value().as_deref(),
+            Some("db;dur=3.000, render;dur=0.500")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_not_collected_outside_scope() {
+        record("db", Duration::from_millis(1));
+        assert_eq!(ServerTiming::default().header_value(), None);
+    }
+}
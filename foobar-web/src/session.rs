@@ -0,0 +1,235 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Sessions stored in HMAC signed cookie
+//!
+//! Session data is small set of string values stored on the client
+//! side, so no server side store is needed. Signature prevents the
+//! client from modifying the data, but does not hide it, so secrets
+//! must not be stored in the session.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use tower_cookies::cookie::SameSite;
+use tower_cookies::{Cookie, Cookies, Key};
+use tracing::warn;
+use url::form_urlencoded;
+
+const SESSION_COOKIE: &str = "session";
+
+/// Minimal length of configured session key, in bytes
+pub const MIN_KEY_LENGTH: usize = 32;
+
+/// Keys for signing and verifying session cookies
+#[derive(Clone)]
+pub struct SessionKeys {
+    current: Key,
+    previous: Arc<[Key]>,
+}
+
+impl SessionKeys {
+    /// Derive keys from configured secrets
+    ///
+    /// Cookies are signed with `current` key, and `previous` keys are
+    /// only accepted for verification, which allows to rotate keys
+    /// without invalidating existing sessions. If no key is configured,
+    /// random one is generated, so sessions do not survive restart.
+    pub fn new(current: Option<&str>, previous: &[String]) -> Self {
+        let current = match current {
+            Some(secret) => Key::derive_from(secret.as_bytes()),
+            None => {
+                warn!("session key is not configured, sessions will not persist across restarts");
+                Key::generate()
+            }
+        };
+        Self {
+            current,
+            previous: previous
+                .iter()
+                .map(|secret| Key::derive_from(secret.as_bytes()))
+                .collect(),
+        }
+    }
+}
+
+/// Extractor for the session of current client
+///
+/// Changes are written into the response cookie right away.
+pub struct Session {
+    cookies: Cookies,
+    keys: SessionKeys,
+    values: BTreeMap<String, String>,
+}
+
+impl Session {
+    fn load(cookies: Cookies, keys: SessionKeys) -> Self {
+        let mut resign = false;
+        let mut cookie = cookies.signed(&keys.current).get(SESSION_COOKIE);
+        for key in keys.previous.iter() {
+            if cookie.is_some() {
+                break;
+            }
+            cookie = cookies.signed(key).get(SESSION_COOKIE);
+            resign = true;
+        }
+
+        let values = cookie
+            .map(|cookie| {
+                form_urlencoded::parse(cookie.value().as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let session = Self {
+            cookies,
+            keys,
+            values,
+        };
+        if resign && !session.values.is_empty() {
+            session.save();
+        }
+        session
+    }
+
+    fn save(&self) {
+        if self.values.is_empty() {
+            self.cookies
+                .remove(Cookie::build(SESSION_COOKIE).path("/").build());
+            return;
+        }
+
+        let value = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.values)
+            .finish();
+        let cookie = Cookie::build((SESSION_COOKIE, value))
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .build();
+        self.cookies.signed(&self.keys.current).add(cookie);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+        self.save();
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        if self.values.remove(name).is_some() {
+            self.save();
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for Session
+where
+    S: Send + Sync,
+    SessionKeys: FromRef<S>,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let cookies = Cookies::from_request_parts(parts, state).await?;
+        Ok(Self::load(cookies, SessionKeys::from_ref(state)))
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use axum::Router;
+    use axum::extract::Path;
+    use axum::routing::get;
+    use axum_test::TestServer;
+    use tower_cookies::CookieManagerLayer;
+
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef";
+    const OTHER_KEY: &str = "fedcba9876543210fedcba9876543210";
+
+    fn server(keys: SessionKeys) -> TestServer {
+        let app = Router::new()
+            .route(
+                "/get/{name}",
+                get(|session: Session, Path(name): Path<String>| async move {
+                    session.get(&name).unwrap_or("<none>").to_string()
+                }),
+            )
+            .route(
+                "/set/{name}/{value}",
+                get(
+                    |mut session: Session, Path((name, value)): Path<(String, String)>| async move {
+                        session.set(name, value);
+                    },
+                ),
+            )
+            .route(
+                "/remove/{name}",
+                get(
+                    |mut session: Session, Path(name): Path<String>| async move {
+                        session.remove(&name);
+                    },
+                ),
+            )
+            .layer(CookieManagerLayer::new())
+            .with_state(keys);
+        TestServer::builder().save_cookies().build(app)
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        let server = server(SessionKeys::new(Some(KEY), &[]));
+        server.get("/get/foo").await.assert_text("<none>");
+        server.get("/set/foo/bar%20baz").await.assert_status_ok();
+        server.get("/set/qux/1").await.assert_status_ok();
+        server.get("/get/foo").await.assert_text("bar baz");
+        server.get("/get/qux").await.assert_text("1");
+        server.get("/remove/foo").await.assert_status_ok();
+        server.get("/get/foo").await.assert_text("<none>");
+        server.get("/get/qux").await.assert_text("1");
+    }
+
+    #[tokio::test]
+    async fn test_tampered_cookie() {
+        let mut server = server(SessionKeys::new(Some(KEY), &[]));
+        let response = server.get("/set/user/alice").await;
+        let cookie = response.cookie(SESSION_COOKIE);
+        let tampered = Cookie::new(SESSION_COOKIE, cookie.value().replace("alice", "admin"));
+
+        server.clear_cookies();
+        server
+            .get("/get/user")
+            .add_cookie(tampered)
+            .await
+            .assert_text("<none>");
+    }
+
+    #[tokio::test]
+    async fn test_key_rotation() {
+        let old_server = server(SessionKeys::new(Some(OTHER_KEY), &[]));
+        let cookie = old_server.get("/set/foo/bar").await.cookie(SESSION_COOKIE);
+
+        let new_server = server(SessionKeys::new(Some(KEY), &[OTHER_KEY.to_string()]));
+        let response = new_server.get("/get/foo").add_cookie(cookie.clone()).await;
+        response.assert_text("bar");
+        // cookie is re-signed with the current key
+        assert_ne!(response.cookie(SESSION_COOKIE).value(), cookie.value());
+
+        let unrelated_server = server(SessionKeys::new(Some(KEY), &[]));
+        unrelated_server
+            .get("/get/foo")
+            .add_cookie(cookie)
+            .await
+            .assert_text("<none>");
+    }
+}
@@ -1,14 +1,190 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use sqlx::PgPool;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::extract::FromRef;
+use axum::http::HeaderValue;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Postgres, Transaction};
+use tokio::sync::Semaphore;
+
+use foobar_common::{DEFAULT_SETTINGS_CACHE_TTL, SettingsCache, SharedClock, SystemClock};
+
+use crate::config::AppConfig;
+use crate::jwt::JwtVerifier;
+use crate::session::SessionKeys;
+use crate::template::{DEFAULT_SITE_NAME, TemplateContext};
+
+/// Shared flag telling whether the application has finished startup
+///
+/// Until it's set, all routes except infrastructure ones (such
+/// as health check) respond with 503.
+#[derive(Clone, Default)]
+pub struct Readiness(Arc<AtomicBool>);
+
+impl Readiness {
+    pub fn set_ready(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// Create small dedicated pool sharing connection options with the main one
+fn create_admin_pool(pool: &PgPool, size: NonZeroU32) -> PgPool {
+    let connect_options = (*pool.connect_options())
+        .clone()
+        .application_name("foobar-web-admin");
+    PgPoolOptions::new()
+        .max_connections(size.get())
+        .test_before_acquire(true)
+        .connect_lazy_with(connect_options)
+}
 
 pub struct AppState {
     pub pool: PgPool,
+    /// Pool for admin endpoints, so these are not starved by regular
+    /// traffic; same as `pool` unless dedicated one is configured
+    pub admin_pool: PgPool,
+    pub config: AppConfig,
+    pub readiness: Readiness,
+    /// Source of current time, replaceable in tests
+    pub clock: SharedClock,
+    /// Cache of runtime settings, such as feature flags
+    pub settings: SettingsCache,
+    /// Permits for concurrently processed requests, if limited
+    pub request_limiter: Option<Arc<Semaphore>>,
+    pub session_keys: SessionKeys,
+    pub template_context: Arc<TemplateContext>,
+    pub jwt_verifier: Option<Arc<JwtVerifier>>,
+    /// Value of `Link` header for preloading static files
+    pub preload_links: Option<HeaderValue>,
 }
 
 impl AppState {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, config: AppConfig, readiness: Readiness) -> Self {
+        let session_keys =
+            SessionKeys::new(config.session_key.as_deref(), &config.previous_session_keys);
+        let template_context = Arc::new(TemplateContext {
+            base_url: config.base_url.clone(),
+            site_name: config
+                .site_name
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SITE_NAME.to_string()),
+            site_tagline: config.site_tagline.clone(),
+        });
+        let admin_pool = config
+            .admin_pool_size
+            .map_or_else(|| pool.clone(), |size| create_admin_pool(&pool, size));
+        let request_limiter = config
+            .max_concurrent_requests
+            .map(|limit| Arc::new(Semaphore::new(limit.get())));
+        Self {
+            pool,
+            admin_pool,
+            config,
+            readiness,
+            clock: SystemClock::shared(),
+            settings: SettingsCache::new(DEFAULT_SETTINGS_CACHE_TTL),
+            request_limiter,
+            session_keys,
+            template_context,
+            jwt_verifier: None,
+            preload_links: None,
+        }
+    }
+
+    /// Begin a read only transaction
+    ///
+    /// Handlers which only read data should run their queries in
+    /// such transaction, which guards from accidental modifications.
+    /// Transaction is rolled back when dropped, which is fine as
+    /// there's nothing to commit.
+    pub async fn read_tx(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin_with("BEGIN READ ONLY").await
+    }
+
+    /// Begin a read only transaction in the admin pool
+    pub async fn admin_read_tx(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.admin_pool.begin_with("BEGIN READ ONLY").await
+    }
+}
+
+impl FromRef<Arc<AppState>> for SessionKeys {
+    fn from_ref(state: &Arc<AppState>) -> Self {
+        state.session_keys.clone()
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::time::Duration;
+
+    use sqlx::postgres::PgConnectOptions;
+
+    use super::*;
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_read_tx_allows_reads(pool: PgPool) {
+        let state = AppState::new(pool, AppConfig::default(), Readiness::default());
+        let mut tx = state.read_tx().await.unwrap();
+        let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_read_tx_rejects_writes(pool: PgPool) {
+        let state = AppState::new(pool, AppConfig::default(), Readiness::default());
+        let mut tx = state.read_tx().await.unwrap();
+        let err = sqlx::query("INSERT INTO items(text) VALUES('foo')")
+            .execute(&mut *tx)
+            .await
+            .unwrap_err();
+        // read_only_sql_transaction
+        assert_eq!(
+            err.as_database_error()
+                .and_then(|err| err.code())
+                .as_deref(),
+            Some("25006")
+        );
+    }
+
+    #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+    async fn test_admin_pool_not_blocked_by_main_pool(
+        pool_options: PgPoolOptions,
+        connect_options: PgConnectOptions,
+    ) {
+        let pool = pool_options
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(200))
+            .connect_lazy_with(connect_options);
+        let config = AppConfig {
+            admin_pool_size: NonZeroU32::new(1),
+            ..Default::default()
+        };
+        let state = AppState::new(pool, config, Readiness::default());
+
+        // exhaust the main pool
+        let _conn = state.pool.acquire().await.unwrap();
+        assert!(matches!(
+            state.read_tx().await.unwrap_err(),
+            sqlx::Error::PoolTimedOut
+        ));
+
+        let mut tx = state.admin_read_tx().await.unwrap();
+        let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
     }
 }
@@ -108,6 +108,18 @@ impl StaticFiles {
     }
 }
 
+/// Check that requested static file name cannot escape the static directory
+///
+/// Lookups among embedded files are safe by construction, but the names
+/// should be validated anyway, before any lookup, so that serving files
+/// from disk cannot become vulnerable to path traversal.
+pub fn is_safe_file_name(file_name: &str) -> bool {
+    !file_name.is_empty()
+        && !file_name.starts_with('/')
+        && !file_name.contains(['\\', '\0'])
+        && !file_name.split('/').any(|component| component == "..")
+}
+
 pub fn url_for_static(file_name: &str) -> anyhow::Result<String> {
     let file = STATIC_FILES
         .by_orig_name(file_name)
@@ -120,6 +132,22 @@ pub fn url_for_static(file_name: &str) -> anyhow::Result<String> {
         .build()?)
 }
 
+/// Format `Link` header entry for preloading given static file
+pub fn preload_link(file_name: &str) -> anyhow::Result<String> {
+    let destination = match file_name.rsplit_once(".").map(|(_, ext)| ext).unwrap_or("") {
+        "css" => "style",
+        "js" => "script",
+        "ico" | "png" | "svg" => "image",
+        // fonts are always fetched in CORS mode
+        "woff" | "woff2" => "font; crossorigin",
+        _ => anyhow::bail!("cannot preload static file \"{file_name}\" of unknown type"),
+    };
+    Ok(format!(
+        "<{}>; rel=preload; as={destination}",
+        url_for_static(file_name)?
+    ))
+}
+
 #[allow(unused)]
 pub fn url_for_unversioned_static(file_name: &str) -> anyhow::Result<String> {
     Ok(crate::routes::Route::StaticFile
@@ -128,3 +156,36 @@ pub fn url_for_unversioned_static(file_name: &str) -> anyhow::Result<String> {
         .expect("file_name parameter should exist for StaticFile route")
         .build()?)
 }
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preload_link() {
+        let link = preload_link("amdmi3.min.css").unwrap();
+        assert!(link.starts_with("</static/amdmi3.min."));
+        assert!(link.ends_with(".css>; rel=preload; as=style"));
+        assert!(preload_link("nonexistent.css").is_err());
+    }
+
+    #[test]
+    fn test_safe_file_name() {
+        assert!(is_safe_file_name("amdmi3.min.css"));
+        assert!(is_safe_file_name("amdmi3.min.0123456789abcdef.css"));
+        assert!(is_safe_file_name("images/logo.png"));
+        assert!(is_safe_file_name("file..name.txt"));
+    }
+
+    #[test]
+    fn test_unsafe_file_name() {
+        assert!(!is_safe_file_name(""));
+        assert!(!is_safe_file_name(".."));
+        assert!(!is_safe_file_name("../../etc/passwd"));
+        assert!(!is_safe_file_name("images/../../etc/passwd"));
+        assert!(!is_safe_file_name("/etc/passwd"));
+        assert!(!is_safe_file_name("..\\..\\windows\\win.ini"));
+        assert!(!is_safe_file_name("amdmi3.min.css\0.png"));
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Instant;
+
+use askama::Template;
+use axum::response::{Html, IntoResponse};
+use metrics::counter;
+
+use crate::base_url::BaseUrl;
+use crate::result::HandlerResult;
+use crate::server_timing;
+
+/// Site name used when not configured
+pub const DEFAULT_SITE_NAME: &str = "Foobar Demo";
+
+/// Deployment specific data available to all templates
+pub struct TemplateContext {
+    /// For constructing absolute links, which are not available if not set
+    pub base_url: Option<BaseUrl>,
+    pub site_name: String,
+    pub site_tagline: Option<String>,
+}
+
+impl TemplateContext {
+    /// HTML page title, combining given page title with site name
+    pub fn page_title(&self, title: Option<&str>) -> String {
+        match title {
+            Some(title) => format!("{title} - {}", self.site_name),
+            None => self.site_name.clone(),
+        }
+    }
+}
+
+/// Number of data entries above which templates are rendered off the async executor
+///
+/// Rendering is CPU bound, so large renders done inline block the
+/// executor thread and other requests served by it, while small ones
+/// are not worth the overhead of moving to a blocking thread.
+pub const OFFLOAD_RENDER_THRESHOLD: usize = 50;
+
+/// Render template into HTML response
+///
+/// Render failures are counted per template, so these can be told
+/// apart from other internal errors such as database failures.
+pub fn render_html<T: Template>(name: &'static str, template: &T) -> HandlerResult {
+    let started = Instant::now();
+    let result = template.render();
+    server_timing::record("render", started.elapsed());
+    match result {
+        Ok(html) => Ok(Html(html).into_response()),
+        Err(err) => {
+            counter!("foobar_web_template_render_errors_total", "template" => name).increment(1);
+            Err(err.into())
+        }
+    }
+}
+
+/// Render template into HTML response, offloading large renders
+///
+/// `size` is the number of data entries (such as list items) the
+/// template renders. If it exceeds [`OFFLOAD_RENDER_THRESHOLD`], the
+/// template is rendered in a blocking thread pool.
+pub async fn render_html_sized<T>(name: &'static str, template: T, size: usize) -> HandlerResult
+where
+    T: Template + Send + 'static,
+{
+    if size <= OFFLOAD_RENDER_THRESHOLD {
+        return render_html(name, &template);
+    }
+    // blocking thread does not see request's timings, so it's measured here
+    server_timing::measure(
+        "render",
+        tokio::task::spawn_blocking(move || render_html(name, &template)),
+    )
+    .await?
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread::ThreadId;
+
+    use axum::http::StatusCode;
+    use metrics_util::debugging::DebuggingRecorder;
+
+    use foobar_common::testing::counter_values_by_label;
+
+    use super::*;
+
+    #[derive(Template)]
+    #[template(source = "{{ self.fail()? }}", ext = "html")]
+    struct FailingTemplate;
+
+    impl FailingTemplate {
+        fn fail(&self) -> askama::Result<&'static str> {
+            Err(askama::Error::Fmt)
+        }
+    }
+
+    #[derive(Template)]
+    #[template(source = "<p>ok</p>", ext = "html")]
+    struct OkTemplate;
+
+    /// Template which remembers the thread it was rendered on
+    #[derive(Template)]
+    #[template(source = "{{ self.record_thread() }}", ext = "html")]
+    struct ThreadTemplate(Arc<Mutex<Option<ThreadId>>>);
+
+    impl ThreadTemplate {
+        fn record_thread(&self) -> &'static str {
+            *self.0.lock().unwrap() = Some(std::thread::current().id());
+            ""
+        }
+    }
+
+    #[tokio::test]
+    async fn test_large_renders_offloaded() {
+        // current thread runtime, so anything rendered inline runs on this thread
+        let test_thread = std::thread::current().id();
+        let render_thread = Arc::new(Mutex::new(None));
+
+        let template = ThreadTemplate(render_thread.clone());
+        assert!(
+            render_html_sized("thread.html", template, OFFLOAD_RENDER_THRESHOLD)
+                .await
+                .is_ok()
+        );
+        assert_eq!(*render_thread.lock().unwrap(), Some(test_thread));
+
+        let template = ThreadTemplate(render_thread.clone());
+        assert!(
+            render_html_sized("thread.html", template, OFFLOAD_RENDER_THRESHOLD + 1)
+                .await
+                .is_ok()
+        );
+        let offloaded_thread = render_thread.lock().unwrap().unwrap();
+        assert_ne!(offloaded_thread, test_thread);
+    }
+
+    #[test]
+    fn test_page_title() {
+        let ctx = TemplateContext {
+            base_url: None,
+            site_name: "Example".to_string(),
+            site_tagline: None,
+        };
+        assert_eq!(ctx.page_title(Some("About")), "About - Example");
+        assert_eq!(ctx.page_title(None), "Example");
+    }
+
+    #[test]
+    fn test_render_errors_counted() {
+        let recorder = DebuggingRecorder::new();
+
+        metrics::with_local_recorder(&recorder, || {
+            assert!(render_html("ok.html", &OkTemplate).is_ok());
+            let response = render_html("failing.html", &FailingTemplate)
+                .unwrap_err()
+                .into_response();
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        });
+
+        assert_eq!(
+            counter_values_by_label(
+                &recorder,
+                "foobar_web_template_render_errors_total",
+                "template"
+            ),
+            vec![("failing.html".to_string(), 1)]
+        );
+    }
+}
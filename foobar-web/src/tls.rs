@@ -0,0 +1,248 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::serve::Listener;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use tokio::task::JoinSet;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tracing::{debug, error, info};
+
+/// Time allowed for client to complete TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Paths to PEM encoded certificate chain and private key
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+fn crypto_provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> anyhow::Result<CertifiedKey> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("cannot load certificates from {}", cert_path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates found in {}", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("cannot load private key from {}", key_path.display()))?;
+    CertifiedKey::from_der(certs, key, provider).context("certificate does not match private key")
+}
+
+/// Certificate resolver which allows replacing the certificate at runtime
+#[derive(Debug)]
+pub struct ReloadableCertResolver {
+    config: TlsConfig,
+    provider: Arc<CryptoProvider>,
+    certified_key: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ReloadableCertResolver {
+    /// Load certificate and key, failing if they do not match
+    pub fn new(config: TlsConfig) -> anyhow::Result<Self> {
+        let provider = crypto_provider();
+        let certified_key = load_certified_key(&config.cert, &config.key, &provider)?;
+        Ok(Self {
+            config,
+            provider,
+            certified_key: RwLock::new(Arc::new(certified_key)),
+        })
+    }
+
+    /// Reload certificate and key from files
+    ///
+    /// Previous certificate stays in use if loading fails.
+    pub fn reload(&self) -> anyhow::Result<()> {
+        let certified_key =
+            load_certified_key(&self.config.cert, &self.config.key, &self.provider)?;
+        *self
+            .certified_key
+            .write()
+            .expect("certificate lock should not be poisoned") = Arc::new(certified_key);
+        Ok(())
+    }
+
+    /// Create acceptor offering given ALPN protocols, in order of preference
+    pub fn acceptor(self: &Arc<Self>, alpn_protocols: &[&[u8]]) -> anyhow::Result<TlsAcceptor> {
+        let mut config = rustls::ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(self.clone());
+        config.alpn_protocols = alpn_protocols
+            .iter()
+            .map(|protocol| protocol.to_vec())
+            .collect();
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+impl ResolvesServerCert for ReloadableCertResolver {
+    fn resolve(&self, _: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(
+            self.certified_key
+                .read()
+                .expect("certificate lock should not be poisoned")
+                .clone(),
+        )
+    }
+}
+
+type HandshakeResult<L> = io::Result<(TlsStream<<L as Listener>::Io>, <L as Listener>::Addr)>;
+
+async fn handshake<Io, Addr>(
+    acceptor: TlsAcceptor,
+    stream: Io,
+    addr: Addr,
+) -> io::Result<(TlsStream<Io>, Addr)>
+where
+    Io: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let stream = tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))??;
+    Ok((stream, addr))
+}
+
+/// Listener which performs TLS handshake on connections from the inner listener
+///
+/// Handshakes are performed concurrently, so slow clients do not
+/// block accepting other connections; connections which fail the
+/// handshake are dropped.
+pub struct TlsListener<L: Listener> {
+    inner: L,
+    acceptor: TlsAcceptor,
+    pending: JoinSet<HandshakeResult<L>>,
+}
+
+impl<L: Listener> TlsListener<L> {
+    pub fn new(inner: L, acceptor: TlsAcceptor) -> Self {
+        Self {
+            inner,
+            acceptor,
+            pending: JoinSet::new(),
+        }
+    }
+}
+
+impl<L> Listener for TlsListener<L>
+where
+    L: Listener,
+    L::Addr: 'static,
+{
+    type Io = TlsStream<L::Io>;
+    type Addr = L::Addr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            tokio::select! {
+                (stream, addr) = self.inner.accept() => {
+                    self.pending.spawn(handshake(self.acceptor.clone(), stream, addr));
+                }
+                Some(res) = self.pending.join_next() => match res {
+                    Ok(Ok(accepted)) => return accepted,
+                    Ok(Err(error)) => debug!(%error, "TLS handshake failed"),
+                    Err(error) => debug!(%error, "TLS handshake task failed"),
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// Reload certificate whenever SIGHUP is received
+pub async fn reload_on_sighup(resolver: Arc<ReloadableCertResolver>) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut sighup = signal(SignalKind::hangup()).expect("SIGHUP handler should be installable");
+    while sighup.recv().await.is_some() {
+        info!("reloading TLS certificate");
+        if let Err(error) = resolver.reload() {
+            error!(
+                error = format!("{error:#}"),
+                "cannot reload TLS certificate, keeping the old one"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use foobar_common::testing::temp_dir;
+
+    use super::*;
+
+    fn write_cert(dir: &Path, name: &str) -> TlsConfig {
+        let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let config = TlsConfig {
+            cert: dir.join(format!("{name}.crt")),
+            key: dir.join(format!("{name}.key")),
+        };
+        std::fs::write(&config.cert, generated.cert.pem()).unwrap();
+        std::fs::write(&config.key, generated.signing_key.serialize_pem()).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_mismatched_key() {
+        let dir = temp_dir();
+        let first = write_cert(dir.path(), "first");
+        let second = write_cert(dir.path(), "second");
+        let config = TlsConfig {
+            cert: first.cert,
+            key: second.key,
+        };
+        let err = ReloadableCertResolver::new(config).unwrap_err();
+        assert!(format!("{err:#}").contains("does not match"));
+    }
+
+    #[test]
+    fn test_missing_files() {
+        let config = TlsConfig {
+            cert: "/nonexistent.crt".into(),
+            key: "/nonexistent.key".into(),
+        };
+        assert!(ReloadableCertResolver::new(config).is_err());
+    }
+
+    #[test]
+    fn test_reload() {
+        let dir = temp_dir();
+        let config = write_cert(dir.path(), "reload");
+        let resolver = ReloadableCertResolver::new(config.clone()).unwrap();
+        let current = || resolver.certified_key.read().unwrap().cert.clone();
+        let original = current();
+
+        // broken files do not replace the working certificate
+        std::fs::write(&config.key, "garbage").unwrap();
+        assert!(resolver.reload().is_err());
+        assert_eq!(current(), original);
+
+        let replacement = write_cert(dir.path(), "reload");
+        assert_eq!(replacement.cert, config.cert);
+        resolver.reload().unwrap();
+        assert_ne!(current(), original);
+    }
+}
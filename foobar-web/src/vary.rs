@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+
+/// Mark response as varying on the given request header
+///
+/// Must be called by every handler which negotiates response content
+/// (e.g. on `Accept-Encoding`), regardless of which variant was chosen,
+/// otherwise caches may serve a wrong variant. Merges with values
+/// already present in the `Vary` header, skipping duplicates.
+pub fn add_vary(headers: &mut HeaderMap, name: HeaderName) {
+    let mut names: Vec<String> = headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+
+    if names
+        .iter()
+        .any(|item| item == "*" || item.eq_ignore_ascii_case(name.as_str()))
+    {
+        return;
+    }
+
+    names.push(name.to_string());
+
+    headers.insert(
+        header::VARY,
+        HeaderValue::from_str(&names.join(", "))
+            .expect("header names should form a valid header value"),
+    );
+}
+
+#[cfg(test)]
+#[coverage(off)]
+mod tests {
+    use super::*;
+
+    fn vary(values: &[&'static str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(header::VARY, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_add() {
+        let mut headers = HeaderMap::new();
+        add_vary(&mut headers, header::ACCEPT);
+        assert_eq!(headers[header::VARY], "accept");
+    }
+
+    #[test]
+    fn test_merge() {
+        // multiple header lines are collapsed into one
+        let mut headers = vary(&["Origin", "Cookie, User-Agent"]);
+        add_vary(&mut headers, header::ACCEPT_ENCODING);
+        assert_eq!(
+            headers.get_all(header::VARY).iter().collect::<Vec<_>>(),
+            vec!["Origin, Cookie, User-Agent, accept-encoding"]
+        );
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let mut headers = vary(&["Accept-Encoding, Origin"]);
+        add_vary(&mut headers, header::ACCEPT_ENCODING);
+        assert_eq!(headers[header::VARY], "Accept-Encoding, Origin");
+
+        add_vary(&mut headers, header::ACCEPT);
+        add_vary(&mut headers, header::ACCEPT);
+        assert_eq!(headers[header::VARY], "Accept-Encoding, Origin, accept");
+    }
+
+    #[test]
+    fn test_wildcard() {
+        // response already varies on everything
+        let mut headers = vary(&["*"]);
+        add_vary(&mut headers, header::ACCEPT);
+        assert_eq!(headers[header::VARY], "*");
+    }
+}
@@ -8,11 +8,13 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
 async fn test_about(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/about").await;
     response.assert_status_ok();
     response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_header("cache-control", "no-cache");
     response.assert_text_contains("example about page");
+    response.assert_text_contains("<title>About - Foobar Demo</title>");
     assert!(
         !tidier::Doc::new(response.text(), false)
             .unwrap()
@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+use foobar_common::{MIGRATOR, hash_api_key};
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+fn config() -> AppConfig {
+    AppConfig {
+        admin_token: Some("secret".to_string()),
+        api_key_hashes: vec![hash_api_key("api-key")],
+        ..Default::default()
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_admin_migrations(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+    let response = server
+        .get("/admin/migrations")
+        .authorization_bearer("secret")
+        .await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+
+    let body: Value = response.json();
+    assert_eq!(body["up_to_date"], json!(true));
+
+    let reported: Vec<(i64, &str)> = body["migrations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|migration| {
+            assert_eq!(migration["state"], json!("applied"));
+            (
+                migration["version"].as_i64().unwrap(),
+                migration["description"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    let known: Vec<(i64, &str)> = MIGRATOR
+        .iter()
+        .map(|migration| (migration.version, &*migration.description))
+        .collect();
+    assert_eq!(reported, known);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_admin_migrations_pending(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+    let response = server
+        .get("/admin/migrations")
+        .authorization_bearer("secret")
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    assert_eq!(body["up_to_date"], json!(false));
+    assert_eq!(body["migrations"][0]["state"], json!("pending"));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_admin_migrations_unauthorized(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+
+    let response = server.get("/admin/migrations").await;
+    response.assert_status_unauthorized();
+    response.assert_header("www-authenticate", "Bearer");
+
+    let response = server
+        .get("/admin/migrations")
+        .authorization_bearer("wrong")
+        .await;
+    response.assert_status_unauthorized();
+
+    let response = server
+        .get("/admin/migrations")
+        .add_header("x-api-key", "wrong")
+        .await;
+    response.assert_status_unauthorized();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_admin_migrations_api_key(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+
+    server
+        .get("/admin/migrations")
+        .add_header("x-api-key", "api-key")
+        .await
+        .assert_status_ok();
+
+    server
+        .get("/admin/migrations")
+        .authorization_bearer("api-key")
+        .await
+        .assert_status_ok();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_admin_migrations_api_key_only(pool: PgPool) {
+    let config = AppConfig {
+        api_key_hashes: vec![hash_api_key("api-key")],
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+
+    server
+        .get("/admin/migrations")
+        .add_header("x-api-key", "api-key")
+        .await
+        .assert_status_ok();
+
+    // admin token is not configured, so it's not accepted in any form
+    server
+        .get("/admin/migrations")
+        .authorization_bearer("")
+        .await
+        .assert_status_unauthorized();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_admin_migrations_disabled(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/admin/migrations")
+        .authorization_bearer("secret")
+        .await;
+    response.assert_status_not_found();
+}
@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_api_docs(pool: PgPool) {
+    let config = AppConfig {
+        api_docs: true,
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+    let response = server.get("/api/docs").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_text_contains(r#"<a href="/openapi.json">"#);
+    response.assert_text_contains("<code>/admin/migrations</code>");
+    assert!(
+        !tidier::Doc::new(response.text(), false)
+            .unwrap()
+            .has_issues()
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_api_docs_disabled(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    server.get("/api/docs").await.assert_status_not_found();
+}
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_concurrency_limit(pool: PgPool) {
+    let tiny_pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect_with((*pool.connect_options()).clone())
+        .await
+        .unwrap();
+
+    // hold the only connection, so requests are stuck waiting for it
+    let conn = tiny_pool.acquire().await.unwrap();
+
+    let config = AppConfig {
+        max_concurrent_requests: NonZeroUsize::new(2),
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(tiny_pool, config).await.unwrap());
+
+    let burst = async {
+        // let stuck requests take all the permits
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let response = server.get("/").expect_failure().await;
+        response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        response.assert_header("retry-after", "1");
+
+        // infrastructure endpoints are not limited
+        server.get("/health").await.assert_status_ok();
+
+        drop(conn);
+    };
+    let (first, second, ()) = tokio::join!(server.get("/"), server.get("/"), burst);
+    first.assert_status_ok();
+    second.assert_status_ok();
+
+    // permits are released after requests complete
+    server.get("/").await.assert_status_ok();
+}
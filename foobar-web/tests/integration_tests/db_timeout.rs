@@ -0,0 +1,30 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_db_timeout(pool: PgPool) {
+    let server = TestServer::new(create_app(pool.clone(), Default::default()).await.unwrap());
+
+    // make queries to items block for as long as the transaction is open
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("LOCK TABLE items IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let start = Instant::now();
+    let response = server.get("/").await;
+    response.assert_status(StatusCode::GATEWAY_TIMEOUT);
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    tx.rollback().await.unwrap();
+    server.get("/").await.assert_status_ok();
+}
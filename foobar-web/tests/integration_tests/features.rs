@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+fn config() -> AppConfig {
+    AppConfig {
+        admin_token: Some("secret".to_string()),
+        ..Default::default()
+    }
+}
+
+const ABOUT_LINK: &str = r#"<a href="/about">About</a>"#;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_feature_toggle(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+
+    server.get("/about").await.assert_status_ok();
+    server.get("/").await.assert_text_contains(ABOUT_LINK);
+
+    let response = server
+        .put("/admin/features/about_page")
+        .authorization_bearer("secret")
+        .text("off")
+        .await;
+    response.assert_status_ok();
+    response.assert_text("off");
+
+    server.get("/about").await.assert_status_not_found();
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    assert!(!response.text().contains(ABOUT_LINK));
+
+    server
+        .put("/admin/features/about_page")
+        .authorization_bearer("secret")
+        .text("on")
+        .await
+        .assert_status_ok();
+
+    server.get("/about").await.assert_status_ok();
+    server.get("/").await.assert_text_contains(ABOUT_LINK);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_feature_percentage_rollout(pool: PgPool) {
+    let mut server = TestServer::new(create_app(pool, config()).await.unwrap());
+    server.save_cookies();
+
+    server
+        .put("/admin/features/about_page")
+        .authorization_bearer("secret")
+        .text("50%")
+        .await
+        .assert_status_ok();
+
+    // client is assigned a bucket and keeps seeing the same variant
+    let response = server.get("/about").await;
+    assert!(response.maybe_header("set-cookie").is_some());
+    // session cookie must not end up in shared caches
+    response.assert_header("cache-control", "private, no-cache");
+    let first = response.status_code();
+    for _ in 0..5 {
+        assert_eq!(server.get("/about").await.status_code(), first);
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_feature_invalid(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, config()).await.unwrap());
+
+    server
+        .put("/admin/features/unknown")
+        .authorization_bearer("secret")
+        .text("on")
+        .await
+        .assert_status_not_found();
+
+    server
+        .put("/admin/features/about_page")
+        .authorization_bearer("secret")
+        .text("maybe")
+        .await
+        .assert_status_bad_request();
+
+    server
+        .put("/admin/features/about_page")
+        .text("off")
+        .await
+        .assert_status_unauthorized();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_feature_change_not_authorized_by_session(pool: PgPool) {
+    let mut server = TestServer::new(create_app(pool, config()).await.unwrap());
+    server.save_cookies();
+
+    server
+        .put("/admin/features/about_page")
+        .authorization_bearer("secret")
+        .text("50%")
+        .await
+        .assert_status_ok();
+
+    // session cookie is all a browser would attach to a forged
+    // cross-site request, and it grants nothing
+    server.get("/about").await;
+    server
+        .put("/admin/features/about_page")
+        .text("off")
+        .await
+        .assert_status_unauthorized();
+}
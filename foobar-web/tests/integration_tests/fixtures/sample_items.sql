@@ -1,3 +1,3 @@
-INSERT INTO items(text, time) VALUES
-	('Sample item foo', '1970-01-01 00:00:00+00'),
-	('Sample item bar', '1970-01-01 00:00:00+00');
+INSERT INTO items(text, time, created_at, updated_at) VALUES
+	('Sample item foo', '1970-01-01 00:00:00+00', '1970-01-01 00:00:00+00', '1970-01-02 00:00:00+00'),
+	('Sample item bar', '1970-01-01 00:00:00+00', '1970-01-01 00:00:00+00', '1970-01-01 00:00:00+00');
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_conflicting_framing(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/")
+        .add_header("content-length", "0")
+        .add_header("transfer-encoding", "chunked")
+        .await;
+    response.assert_status_bad_request();
+    response.assert_text("Both Content-Length and Transfer-Encoding specified");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_duplicate_content_length(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/")
+        .add_header("content-length", "0")
+        .add_header("content-length", "0")
+        .await;
+    response.assert_status_bad_request();
+    response.assert_text("Multiple Content-Length specified");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_invalid_content_length(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/").add_header("content-length", "+0").await;
+    response.assert_status_bad_request();
+    response.assert_text("Invalid Content-Length");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_valid_content_length(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    server
+        .get("/")
+        .add_header("content-length", "0")
+        .await
+        .assert_status_ok();
+}
@@ -0,0 +1,120 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use serde_json::json;
+use sqlx::PgPool;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_items(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": "{ items { nodes { id text } pageInfo { hasNextPage } } }"}))
+        .await;
+    response.assert_status_ok();
+    response.assert_json(&json!({
+        "data": {
+            "items": {
+                "nodes": [
+                    {"id": 1, "text": "Sample item foo"},
+                    {"id": 2, "text": "Sample item bar"},
+                ],
+                "pageInfo": {"hasNextPage": false},
+            }
+        }
+    }));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_items_pagination(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let query = "query($after: String) { items(first: 1, after: $after) { nodes { id } pageInfo { hasNextPage endCursor } } }";
+
+    let response = server.post("/graphql").json(&json!({"query": query})).await;
+    let page: serde_json::Value = response.json();
+    let items = &page["data"]["items"];
+    assert_eq!(items["nodes"], json!([{"id": 1}]));
+    assert_eq!(items["pageInfo"]["hasNextPage"], true);
+
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": query, "variables": {"after": items["pageInfo"]["endCursor"]}}))
+        .await;
+    let page: serde_json::Value = response.json();
+    let items = &page["data"]["items"];
+    assert_eq!(items["nodes"], json!([{"id": 2}]));
+    assert_eq!(items["pageInfo"]["hasNextPage"], false);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_items_invalid_arguments(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    for query in [
+        "{ items(first: 101) { nodes { id } } }",
+        "{ items(first: -1) { nodes { id } } }",
+        r#"{ items(after: "garbage") { nodes { id } } }"#,
+    ] {
+        let response = server.post("/graphql").json(&json!({"query": query})).await;
+        let response: serde_json::Value = response.json();
+        assert!(response["errors"].is_array(), "{query} should fail");
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_query_limits(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+
+    let deep_query = "{ __schema { types { fields { type { ofType { ofType { ofType { ofType { name } } } } } } } } }";
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": deep_query}))
+        .await;
+    let response: serde_json::Value = response.json();
+    assert_eq!(
+        response["errors"][0]["message"],
+        "Query is nested too deep."
+    );
+
+    let complex_query = format!(
+        "{{ {} }}",
+        (0..10)
+            .map(|i| format!("items{i}: items {{ nodes {{ id text time }} }}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": complex_query}))
+        .await;
+    let response: serde_json::Value = response.json();
+    assert_eq!(response["errors"][0]["message"], "Query is too complex.");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_item(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": "{ item(id: 2) { text time } }"}))
+        .await;
+    response.assert_status_ok();
+    response.assert_json(&json!({
+        "data": {
+            "item": {"text": "Sample item bar", "time": "1970-01-01T00:00:00+00:00"}
+        }
+    }));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_item_not_found(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .post("/graphql")
+        .json(&json!({"query": "{ item(id: 999) { text } }"}))
+        .await;
+    response.assert_status_ok();
+    response.assert_json(&json!({"data": {"item": null}}));
+}
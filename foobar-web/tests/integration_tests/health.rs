@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_health(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/health").await;
+    response.assert_status_ok();
+    response.assert_text("OK");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_health_detailed(pool: PgPool) {
+    sqlx::query("INSERT INTO worker_runs(worker, last_success) VALUES('foo', now())")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/health/detailed").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+
+    let json: serde_json::Value = response.json();
+    assert_eq!(json["status"], "ok");
+    assert_eq!(json["checks"]["database"]["status"], "ok");
+    assert!(json["checks"]["database"]["latency_ms"].is_f64());
+    assert_eq!(json["checks"]["worker_last_run"]["status"], "ok");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_health_detailed_database_failure(pool: PgPool) {
+    let server = TestServer::new(create_app(pool.clone(), Default::default()).await.unwrap());
+    pool.close().await;
+    let response = server.get("/health/detailed").await;
+    response.assert_status_service_unavailable();
+
+    let json: serde_json::Value = response.json();
+    assert_eq!(json["status"], "fail");
+    assert_eq!(json["checks"]["database"]["status"], "fail");
+    assert!(json["checks"]["database"]["latency_ms"].is_f64());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_health_detailed_stale_worker(pool: PgPool) {
+    let server = TestServer::new(create_app(pool.clone(), Default::default()).await.unwrap());
+
+    let response = server.get("/health/detailed").await;
+    response.assert_status_service_unavailable();
+    let json: serde_json::Value = response.json();
+    assert_eq!(json["checks"]["database"]["status"], "ok");
+    assert_eq!(json["checks"]["worker_last_run"]["status"], "fail");
+    assert_eq!(
+        json["checks"]["worker_last_run"]["detail"],
+        "no successful runs recorded"
+    );
+
+    sqlx::query(
+        "INSERT INTO worker_runs(worker, last_success) VALUES('foo', now() - interval '1 hour')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let response = server.get("/health/detailed").await;
+    response.assert_status_service_unavailable();
+    let json: serde_json::Value = response.json();
+    assert_eq!(json["checks"]["worker_last_run"]["status"], "fail");
+}
@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, Version};
+use http_body_util::BodyExt;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use rustls::pki_types::ServerName;
+use sqlx::PgPool;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::TlsConnector;
+
+use foobar_common::testing::temp_dir;
+use foobar_web::create_app;
+use foobar_web::server::ServerConfig;
+use foobar_web::tls::TlsConfig;
+
+use super::running_server::{RunningServer, local_config};
+
+async fn start_server(pool: PgPool, tls: Option<TlsConfig>) -> RunningServer {
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let config = ServerConfig {
+        tls,
+        http2: true,
+        ..local_config().await
+    };
+    RunningServer::start(config, app).await
+}
+
+async fn fetch_index_http2<T>(stream: T, uri: &str)
+where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (mut sender, connection) =
+        hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(stream))
+            .await
+            .expect("HTTP/2 handshake should succeed");
+    tokio::spawn(connection);
+
+    let response = sender
+        .send_request(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.version(), Version::HTTP_2);
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body).contains("Sample item foo"));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_h2c(pool: PgPool) {
+    let server = start_server(pool, None).await;
+
+    let stream = server.connect().await;
+    fetch_index_http2(stream, "http://localhost/").await;
+
+    server.shutdown().await;
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_h2_over_tls(pool: PgPool) {
+    let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let dir = temp_dir();
+    let tls = TlsConfig {
+        cert: dir.path().join("cert.pem"),
+        key: dir.path().join("key.pem"),
+    };
+    std::fs::write(&tls.cert, generated.cert.pem()).unwrap();
+    std::fs::write(&tls.key, generated.signing_key.serialize_pem()).unwrap();
+
+    let server = start_server(pool, Some(tls)).await;
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(generated.cert.der().clone()).unwrap();
+    let mut client_config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    client_config.alpn_protocols = vec![b"h2".to_vec()];
+
+    let stream = server.connect().await;
+    let stream = TlsConnector::from(Arc::new(client_config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .expect("TLS handshake should succeed");
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+    fetch_index_http2(stream, "https://localhost/").await;
+
+    server.shutdown().await;
+}
@@ -4,14 +4,16 @@
 use axum_test::TestServer;
 use sqlx::PgPool;
 
+use foobar_web::config::AppConfig;
 use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_index(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/").await;
     response.assert_status_ok();
     response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_header("cache-control", "no-cache");
     response.assert_text_contains("Sample item foo");
     response.assert_text_contains("Sample item bar");
     assert!(
@@ -20,3 +22,163 @@ async fn test_index(pool: PgPool) {
             .has_issues()
     );
 }
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_empty(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_text_contains("No items yet");
+    assert!(!response.text().contains("<table>"));
+    assert!(
+        !tidier::Doc::new(response.text(), false)
+            .unwrap()
+            .has_issues()
+    );
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_pagination(pool: PgPool) {
+    sqlx::query(
+        "INSERT INTO items(text, time) SELECT 'Paged item ' || n, '1970-01-01 00:00:00+00' FROM generate_series(1, 150) AS n",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    response.assert_text_contains(">Paged item 100<");
+    assert!(!response.text().contains(">Paged item 101<"));
+
+    let text = response.text();
+    let next_url = text
+        .split("href=\"")
+        .filter_map(|part| part.split_once('"'))
+        .map(|(url, _)| url)
+        .find(|url| url.starts_with("/?after="))
+        .expect("next page link should be present")
+        .to_string();
+
+    let response = server.get(&next_url).await;
+    response.assert_status_ok();
+    response.assert_text_contains(">Paged item 101<");
+    response.assert_text_contains(">Paged item 150<");
+    assert!(!response.text().contains(">Paged item 100<"));
+    assert!(!response.text().contains("Next page"));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_pagination_uses_index(pool: PgPool) {
+    let mut conn = pool.acquire().await.unwrap();
+    // table is empty, so make the planner consider the index at all
+    sqlx::raw_sql(indoc::indoc! {r#"
+        SET enable_seqscan = off;
+        SET plan_cache_mode = force_generic_plan;
+        PREPARE next_page(timestamptz, integer, bigint) AS
+            SELECT id, text, time FROM items
+            WHERE (time, id) > ($1, $2)
+            ORDER BY time, id
+            LIMIT $3;
+    "#})
+    .execute(&mut *conn)
+    .await
+    .unwrap();
+
+    let plan: Vec<(String,)> = sqlx::query_as("EXPLAIN EXECUTE next_page(now(), 1, 101)")
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap();
+    let plan: String = plan.into_iter().map(|(line,)| line + "\n").collect();
+    assert!(
+        plan.contains("Index Scan using items_time_id_idx"),
+        "{plan}"
+    );
+    assert!(!plan.contains("Sort"), "{plan}");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_index_malformed_cursor(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    for cursor in ["garbage", "!!!", "MTIz"] {
+        let response = server.get("/").add_query_param("after", cursor).await;
+        response.assert_status_bad_request();
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_site_name(pool: PgPool) {
+    let config = AppConfig {
+        site_name: Some("Example Site".to_string()),
+        site_tagline: Some("Just an example".to_string()),
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    response.assert_text_contains("<title>Items - Example Site</title>");
+    response.assert_text_contains("Just an example");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_index_oversized_query(pool: PgPool) {
+    let config = AppConfig {
+        max_query_length: Some(100),
+        max_query_param_length: Some(50),
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+
+    let response = server
+        .get("/")
+        .add_query_param("after", "A".repeat(60))
+        .await;
+    response.assert_status_bad_request();
+    response.assert_text("Query parameter too long");
+
+    let response = server
+        .get("/")
+        .add_query_param("foo", "A".repeat(40))
+        .add_query_param("bar", "A".repeat(40))
+        .add_query_param("baz", "A".repeat(40))
+        .await;
+    response.assert_status_bad_request();
+    response.assert_text("Query string too long");
+
+    // unknown parameters within limits are ignored
+    let response = server.get("/").add_query_param("foo", "A".repeat(40)).await;
+    response.assert_status_ok();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_preload(pool: PgPool) {
+    let config = AppConfig {
+        preload_assets: vec!["amdmi3.min.css".to_string()],
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    let link = response.header("link");
+    let link = link.to_str().unwrap();
+    assert!(link.starts_with("</static/amdmi3.min."));
+    assert!(link.ends_with(".css>; rel=preload; as=style"));
+
+    // not added to non-HTML responses
+    let response = server.get("/static/amdmi3.min.css").await;
+    response.assert_status_ok();
+    assert!(response.maybe_header("link").is_none());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_preload_unknown_asset(pool: PgPool) {
+    let config = AppConfig {
+        preload_assets: vec!["nonexistent.css".to_string()],
+        ..Default::default()
+    };
+    assert!(create_app(pool, config).await.is_err());
+}
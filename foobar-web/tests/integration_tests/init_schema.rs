@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+
+use foobar_common::SCHEMA;
+use foobar_web::init_schema;
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+async fn exists(pool: &PgPool, object: &str) -> bool {
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT to_regclass($1) IS NOT NULL OR to_regnamespace($1) IS NOT NULL")
+            .bind(object)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    exists
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_init_schema(pool: PgPool) {
+    init_schema(&pool, false, LOCK_TIMEOUT).await.unwrap();
+    assert!(exists(&pool, SCHEMA).await);
+    assert!(exists(&pool, "_sqlx_migrations").await);
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_init_schema_skip_migrations(pool: PgPool) {
+    let err = init_schema(&pool, true, LOCK_TIMEOUT).await.unwrap_err();
+    assert!(format!("{err:#}").contains("database schema is not up to date"));
+
+    // migrations were not attempted
+    assert!(!exists(&pool, SCHEMA).await);
+    assert!(!exists(&pool, "_sqlx_migrations").await);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_init_schema_skip_migrations_up_to_date(pool: PgPool) {
+    init_schema(&pool, true, LOCK_TIMEOUT).await.unwrap();
+}
@@ -8,11 +8,14 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_item(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/item/1").await;
     response.assert_status_ok();
     response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_header("cache-control", "no-cache");
     response.assert_text_contains("Sample item foo");
+    response.assert_text_contains("<strong>Created:</strong> 1970-01-01 00:00");
+    response.assert_text_contains("<strong>Updated:</strong> 1970-01-02 00:00");
     assert!(
         !tidier::Doc::new(response.text(), false)
             .unwrap()
@@ -22,7 +25,15 @@ async fn test_item(pool: PgPool) {
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_item_not_found(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/item/999").await;
     response.assert_status_not_found();
+    assert!(response.maybe_header("cache-control").is_none());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_item_empty(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/item/1").await;
+    response.assert_status_not_found();
 }
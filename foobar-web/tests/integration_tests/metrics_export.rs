@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::http::Method;
+use axum_test::TestServer;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use serde_json::{Value, json};
+
+use foobar_web::metrics_export::metrics_router;
+
+fn server(credentials: Option<&str>) -> TestServer {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    metrics::with_local_recorder(&recorder, || {
+        metrics::counter!("foobar_test_total").increment(1);
+        metrics::counter!("foobar_web_http_requests_total", "route" => "/", "status" => "200")
+            .increment(2);
+    });
+    TestServer::new(metrics_router(recorder.handle(), credentials))
+}
+
+#[tokio::test]
+async fn test_metrics() {
+    let server = server(None);
+    let response = server.get("/metrics").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/plain; version=0.0.4");
+    response.assert_text_contains("foobar_test_total 1");
+}
+
+#[tokio::test]
+async fn test_metrics_json() {
+    let server = server(None);
+    let response = server
+        .get("/metrics")
+        .add_query_param("format", "json")
+        .await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+
+    let families: Value = response.json();
+    let requests = families
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|family| family["name"] == "foobar_web_http_requests_total")
+        .unwrap();
+    assert_eq!(
+        requests,
+        &json!({
+            "name": "foobar_web_http_requests_total",
+            "type": "counter",
+            "series": [
+                {
+                    "labels": {"route": "/", "status": "200"},
+                    "value": 2.0,
+                },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_unknown_format() {
+    let server = server(None);
+    server
+        .get("/metrics")
+        .add_query_param("format", "xml")
+        .await
+        .assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_metrics_head() {
+    let server = server(None);
+    let get_response = server.get("/metrics").await;
+
+    let response = server.method(Method::HEAD, "/metrics").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/plain; version=0.0.4");
+    response.assert_header("content-length", get_response.as_bytes().len().to_string());
+    assert!(response.as_bytes().is_empty());
+}
+
+#[tokio::test]
+async fn test_metrics_auth() {
+    let server = server(Some("user:secret"));
+
+    for method in [Method::GET, Method::HEAD] {
+        let response = server.method(method.clone(), "/metrics").await;
+        response.assert_status_unauthorized();
+        response.assert_header("www-authenticate", r#"Basic realm="metrics""#);
+
+        server
+            .method(method.clone(), "/metrics")
+            .authorization("Basic dXNlcjp3cm9uZw==")
+            .await
+            .assert_status_unauthorized();
+
+        // "user:secret"
+        server
+            .method(method, "/metrics")
+            .authorization("Basic dXNlcjpzZWNyZXQ=")
+            .await
+            .assert_status_ok();
+    }
+}
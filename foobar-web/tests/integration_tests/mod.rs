@@ -2,6 +2,29 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 mod about;
+mod admin_migrations;
+mod api_docs;
+mod concurrency_limit;
+mod db_timeout;
+mod features;
+mod framing;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod health;
+mod http2;
 mod index;
+mod init_schema;
 mod item;
+mod metrics_export;
+mod normalize_path;
+mod openapi;
+mod pool_exhaustion;
+mod problem_details;
+mod running_server;
+mod server_timing;
+mod startup_gate;
 mod static_file;
+mod timeouts;
+mod tls;
+mod unix_socket;
+mod version;
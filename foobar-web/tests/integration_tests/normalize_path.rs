@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum_test::TestServer;
+use http_body_util::BodyExt;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+use foobar_web::config::{AppConfig, PathRedirect};
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_messy_paths_redirect(pool: PgPool) {
+    // test client normalizes URLs itself, so send raw requests to the app
+    let app = create_app(pool, Default::default()).await.unwrap();
+    for (path, canonical) in [
+        ("//about", "/about"),
+        ("/./about", "/about"),
+        ("/item//1", "/item/1"),
+        ("/item/2/../1", "/item/1"),
+        ("/../../about", "/about"),
+        ("//?after=abc", "/?after=abc"),
+        ("/about/", "/about"),
+        ("/item/1/?foo=bar", "/item/1?foo=bar"),
+        ("//about//", "/about"),
+    ] {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT, "{path}");
+        assert_eq!(response.headers()[header::LOCATION], canonical);
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_encoded_slash_not_decoded(pool: PgPool) {
+    let app = create_app(pool, Default::default()).await.unwrap();
+    // encoded slash is part of the segment, so this is not redirected
+    // to /about, but passed to the static file handler which rejects it
+    let request = Request::get("/static/..%2Fabout")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_canonical_path_not_redirected(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    server.get("/about").await.assert_status_ok();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_trailing_slash_unknown_path(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    server.get("/nonexistent/").await.assert_status_not_found();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_temporary_redirect(pool: PgPool) {
+    let config = AppConfig {
+        path_redirect: PathRedirect::Temporary,
+        ..Default::default()
+    };
+    let app = create_app(pool, config).await.unwrap();
+    for (path, canonical) in [("//about", "/about"), ("/about/", "/about")] {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT, "{path}");
+        assert_eq!(response.headers()[header::LOCATION], canonical);
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_redirect_preserves_body(pool: PgPool) {
+    for (path_redirect, status) in [
+        (PathRedirect::Permanent, StatusCode::PERMANENT_REDIRECT),
+        (PathRedirect::Temporary, StatusCode::TEMPORARY_REDIRECT),
+    ] {
+        let config = AppConfig {
+            admin_token: Some("secret".to_string()),
+            path_redirect,
+            ..Default::default()
+        };
+        let app = create_app(pool.clone(), config).await.unwrap();
+        let request = |path: &str| {
+            Request::put(path)
+                .header(header::AUTHORIZATION, "Bearer secret")
+                .body(Body::from("off"))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("/admin/features/about_page/"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), status);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        assert_eq!(location, "/admin/features/about_page");
+
+        // client repeats the request with the same method and body
+        let response = app.oneshot(request(location)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "off");
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use serde_json::Value;
+use sqlx::PgPool;
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+/// Check that value conforms to the subset of JSON schema used in the spec
+///
+/// Unlike JSON schema, undocumented object properties are not allowed,
+/// so a renamed field is noticed even if it's optional.
+fn validate(value: &Value, schema: &Value, path: &str) {
+    match schema["type"].as_str().unwrap() {
+        "object" => {
+            let object = value
+                .as_object()
+                .unwrap_or_else(|| panic!("{path}: not an object"));
+            let properties = schema["properties"].as_object().unwrap();
+            for required in schema["required"].as_array().into_iter().flatten() {
+                let required = required.as_str().unwrap();
+                assert!(object.contains_key(required), "{path}: missing {required}");
+            }
+            for (key, value) in object {
+                let schema = properties
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{path}: undocumented {key}"));
+                validate(value, schema, &format!("{path}.{key}"));
+            }
+        }
+        "array" => {
+            let array = value
+                .as_array()
+                .unwrap_or_else(|| panic!("{path}: not an array"));
+            for (i, item) in array.iter().enumerate() {
+                validate(item, &schema["items"], &format!("{path}[{i}]"));
+            }
+        }
+        "string" => {
+            assert!(value.is_string(), "{path}: not a string");
+            if let Some(variants) = schema["enum"].as_array() {
+                assert!(variants.contains(value), "{path}: {value} not in enum");
+            }
+        }
+        "number" => assert!(value.is_number(), "{path}: not a number"),
+        "integer" => assert!(value.is_i64() || value.is_u64(), "{path}: not an integer"),
+        "boolean" => assert!(value.is_boolean(), "{path}: not a boolean"),
+        other => panic!("{path}: unsupported schema type {other}"),
+    }
+}
+
+/// Request each documented operation and validate its response
+async fn validate_responses(server: &TestServer, spec: &Value) {
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        let operation = &operations["get"];
+        let response = server.get(path).authorization_bearer("secret").await;
+        let status = response.status_code();
+        let documented = &operation["responses"][status.as_str()];
+        assert!(
+            documented.is_object(),
+            "{path}: undocumented status {status}"
+        );
+        let schema = &documented["content"]["application/json"]["schema"];
+        validate(&response.json(), schema, path);
+    }
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_openapi(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/openapi.json").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+
+    let spec: serde_json::Value = response.json();
+    assert_eq!(spec["openapi"], "3.0.3");
+
+    let migrations = &spec["paths"]["/admin/migrations"]["get"];
+    let schema = &migrations["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(schema["properties"]["up_to_date"]["type"], "boolean");
+    assert!(migrations["security"].is_array());
+
+    let version = &spec["paths"]["/version"]["get"];
+    assert!(version["security"].is_null());
+
+    // html pages are not described
+    assert!(spec["paths"]["/"].is_null());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_openapi_responses(pool: PgPool) {
+    let config = AppConfig {
+        admin_token: Some("secret".to_string()),
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool.clone(), config).await.unwrap());
+    let spec: Value = server.get("/openapi.json").await.json();
+    assert!(
+        spec["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .all(|operations| { operations.as_object().unwrap().keys().eq(["get"]) })
+    );
+
+    // no worker runs yet, so detailed health check fails with details
+    validate_responses(&server, &spec).await;
+
+    sqlx::query("INSERT INTO worker_runs(worker, last_success) VALUES('foo', now())")
+        .execute(&pool)
+        .await
+        .unwrap();
+    validate_responses(&server, &spec).await;
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_openapi_trailing_slash(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    // document is a file, so its path is not redirected to
+    server.get("/openapi.json/").await.assert_status_not_found();
+}
@@ -0,0 +1,32 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_pool_exhaustion(pool: PgPool) {
+    let tiny_pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_with((*pool.connect_options()).clone())
+        .await
+        .unwrap();
+
+    // hold the only connection so handlers cannot get one
+    let conn = tiny_pool.acquire().await.unwrap();
+
+    let server = TestServer::new(create_app(tiny_pool, Default::default()).await.unwrap());
+    let response = server.get("/").expect_failure().await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    response.assert_header("retry-after", "5");
+
+    drop(conn);
+    server.get("/").await.assert_status_ok();
+}
@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use serde_json::json;
+use sqlx::PgPool;
+
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_not_found_json(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/item/999")
+        .add_header("accept", "application/json")
+        .await;
+    response.assert_status_not_found();
+    response.assert_header("content-type", "application/problem+json");
+    response.assert_header("vary", "accept");
+    response.assert_json(&json!({
+        "type": "about:blank",
+        "title": "Not Found",
+        "status": 404,
+        "detail": "Item not found",
+    }));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_not_found_browser(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/item/999")
+        .add_header("accept", "text/html,application/json;q=0.9,*/*;q=0.8")
+        .await;
+    response.assert_status_not_found();
+    response.assert_header("vary", "accept");
+    response.assert_text("Item not found");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_success_not_negotiated(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/")
+        .add_header("accept", "application/json")
+        .await;
+    response.assert_status_ok();
+    assert!(response.maybe_header("vary").is_none());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_internal_error_json(pool: PgPool) {
+    let server = TestServer::new(create_app(pool.clone(), Default::default()).await.unwrap());
+    // make all database queries fail
+    pool.close().await;
+
+    let response = server
+        .get("/")
+        .add_header("accept", "application/json")
+        .await;
+    response.assert_status_internal_server_error();
+    response.assert_header("content-type", "application/problem+json");
+    response.assert_json(&json!({
+        "type": "about:blank",
+        "title": "Internal Server Error",
+        "status": 500,
+    }));
+}
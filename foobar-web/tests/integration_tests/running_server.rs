@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Real server for tests which exercise the connection level
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::Router;
+use tokio::net::{TcpListener, TcpStream, UnixStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use foobar_web::server::{ListenAddr, ServerConfig, serve};
+
+/// Config for plain HTTP/1 server on a free local port
+pub async fn local_config() -> ServerConfig {
+    // pick a free port for the server to bind
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    ServerConfig {
+        listen: ListenAddr::Tcp(addr),
+        proxy_protocol: false,
+        tls: None,
+        http2: false,
+        timeouts: Default::default(),
+    }
+}
+
+/// Server running in background task until shut down
+pub struct RunningServer {
+    listen: ListenAddr,
+    shutdown_tx: oneshot::Sender<()>,
+    task: JoinHandle<anyhow::Result<()>>,
+}
+
+impl RunningServer {
+    /// Start serving `app`, waiting until connections are accepted
+    pub async fn start(config: ServerConfig, app: Router) -> Self {
+        let listen = config.listen.clone();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            serve(&config, app, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+        });
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let connected = match &listen {
+                    ListenAddr::Tcp(addr) => TcpStream::connect(addr).await.is_ok(),
+                    ListenAddr::Unix(path) => UnixStream::connect(path).await.is_ok(),
+                };
+                if connected {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("server should start listening");
+
+        Self {
+            listen,
+            shutdown_tx,
+            task,
+        }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        match &self.listen {
+            ListenAddr::Tcp(addr) => *addr,
+            ListenAddr::Unix(_) => panic!("server should listen on TCP"),
+        }
+    }
+
+    pub async fn connect(&self) -> TcpStream {
+        TcpStream::connect(self.addr()).await.unwrap()
+    }
+
+    /// Request graceful shutdown and wait for it to complete
+    pub async fn shutdown(self) {
+        self.shutdown_tx.send(()).unwrap();
+        self.task.await.unwrap().unwrap();
+    }
+}
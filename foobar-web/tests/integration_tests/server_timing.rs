@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_server_timing(pool: PgPool) {
+    let config = AppConfig {
+        server_timing: true,
+        ..Default::default()
+    };
+    let server = TestServer::new(create_app(pool, config).await.unwrap());
+
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    let header = response.header("server-timing");
+    let names: Vec<&str> = header
+        .to_str()
+        .unwrap()
+        .split(", ")
+        .map(|metric| metric.split(';').next().unwrap())
+        .collect();
+    assert_eq!(names, vec!["db", "render"]);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_server_timing_disabled(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+
+    let response = server.get("/").await;
+    response.assert_status_ok();
+    assert!(response.maybe_header("server-timing").is_none());
+}
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::config::AppConfig;
+use foobar_web::{Readiness, create_app_with_readiness};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_not_ready(pool: PgPool) {
+    let readiness = Readiness::default();
+    let config = AppConfig {
+        admin_token: Some("secret".to_string()),
+        ..Default::default()
+    };
+    let server = TestServer::new(
+        create_app_with_readiness(pool, config, readiness)
+            .await
+            .unwrap(),
+    );
+
+    let response = server.get("/").await;
+    response.assert_status_service_unavailable();
+    response.assert_header("retry-after", "5");
+
+    let response = server.get("/static/amdmi3.min.css").await;
+    response.assert_status_service_unavailable();
+
+    let response = server.get("/health").await;
+    response.assert_status_ok();
+
+    let response = server
+        .get("/admin/migrations")
+        .authorization_bearer("secret")
+        .await;
+    response.assert_status_ok();
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_becomes_ready(pool: PgPool) {
+    let readiness = Readiness::default();
+    let server = TestServer::new(
+        create_app_with_readiness(pool, Default::default(), readiness.clone())
+            .await
+            .unwrap(),
+    );
+
+    server.get("/").await.assert_status_service_unavailable();
+    readiness.set_ready();
+    server.get("/").await.assert_status_ok();
+}
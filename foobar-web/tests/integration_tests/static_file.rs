@@ -8,17 +8,93 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
 async fn test_nonexistent(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/static/nonexistent").await;
     response.assert_status_not_found();
+    response.assert_text_contains("Static file not found");
+    // requested name is not reflected into the response
+    assert!(!response.text().contains("nonexistent"));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_case_mismatch(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/static/AMDmi3.min.css").await;
+    response.assert_status_not_found();
+    response.assert_text_contains("Static file not found");
+    response.assert_text_contains("case sensitive");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_path_traversal(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    for path in [
+        "/static/..%2F..%2Fetc%2Fpasswd",
+        "/static/%2e%2e%2f%2e%2e%2fetc%2fpasswd",
+        "/static/%2Fetc%2Fpasswd",
+        "/static/..%5C..%5Cwindows%5Cwin.ini",
+        "/static/amdmi3.min.css%00.png",
+    ] {
+        let response = server.get(path).await;
+        response.assert_status_bad_request();
+    }
 }
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
 async fn test_css(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     let response = server.get("/static/amdmi3.min.css").await;
     response.assert_status_ok();
     response.assert_header("content-type", "text/css");
+    // set by the handler
+    response.assert_header("cache-control", "public, max-age=3600");
     response.assert_text_contains("light-dark");
     assert!(response.text().len() > 1000);
 }
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_vary_uncompressed(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/static/amdmi3.min.css").await;
+    response.assert_status_ok();
+    response.assert_header("vary", "accept-encoding");
+    assert!(response.maybe_header("content-encoding").is_none());
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_vary_compressed(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server
+        .get("/static/amdmi3.min.css")
+        .add_header("accept-encoding", "gzip")
+        .await;
+    response.assert_status_ok();
+    response.assert_header("content-encoding", "gzip");
+    response.assert_header("vary", "accept-encoding");
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_svg_content_type(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/static/favicon.svg").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "image/svg+xml");
+    response.assert_header("x-content-type-options", "nosniff");
+    response.assert_header("content-disposition", "inline");
+    let csp = response.header("content-security-policy");
+    let csp = csp.to_str().unwrap();
+    assert!(csp.contains("default-src 'none'"));
+    assert!(csp.contains("sandbox"));
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_css_content_type(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    let response = server.get("/static/amdmi3.min.css").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/css");
+    response.assert_header("x-content-type-options", "nosniff");
+    assert!(response.maybe_header("content-disposition").is_none());
+    let csp = response.header("content-security-policy");
+    assert!(!csp.to_str().unwrap().contains("sandbox"));
+}
@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::{Duration, Instant};
+
+use axum::Router;
+use axum::routing::get;
+use sqlx::PgPool;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use foobar_web::config::AppConfig;
+use foobar_web::create_app;
+use foobar_web::server::{ServerConfig, ServerTimeouts};
+
+use super::running_server::{RunningServer, local_config};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_stalled_headers(pool: PgPool) {
+    let timeouts = ServerTimeouts {
+        header_read: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let config = ServerConfig {
+        timeouts,
+        ..local_config().await
+    };
+    let server = RunningServer::start(config, app).await;
+    let mut stream = server.connect().await;
+
+    let start = Instant::now();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: local")
+        .await
+        .unwrap();
+
+    // server must respond and close the connection without waiting for the rest of headers
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), stream.read_to_end(&mut response))
+        .await
+        .expect("connection should be closed by the server")
+        .ok();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert!(String::from_utf8_lossy(&response).starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+
+    server.shutdown().await;
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_malformed_request(pool: PgPool) {
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let server = RunningServer::start(local_config().await, app).await;
+    let mut stream = server.connect().await;
+
+    stream
+        .write_all(b"GET /\x01 HTTP/1.1\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), stream.read_to_end(&mut response))
+        .await
+        .expect("connection should be closed by the server")
+        .unwrap();
+    assert!(String::from_utf8_lossy(&response).starts_with("HTTP/1.1 400 Bad Request\r\n"));
+
+    server.shutdown().await;
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_oversized_body(pool: PgPool) {
+    let config = AppConfig {
+        max_body_size: Some(16),
+        ..Default::default()
+    };
+    let app = create_app(pool, config).await.unwrap();
+    let server = RunningServer::start(local_config().await, app).await;
+    let mut stream = server.connect().await;
+
+    stream
+        .write_all(b"POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: 17\r\n\r\n")
+        .await
+        .unwrap();
+
+    // rejected right away, without reading the body
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), stream.read_to_end(&mut response))
+        .await
+        .expect("connection should be closed by the server")
+        .unwrap();
+    assert!(String::from_utf8_lossy(&response).starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+
+    server.shutdown().await;
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_idle_keepalive(pool: PgPool) {
+    let timeouts = ServerTimeouts {
+        keep_alive: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let config = ServerConfig {
+        timeouts,
+        ..local_config().await
+    };
+    let server = RunningServer::start(config, app).await;
+    let mut stream = server.connect().await;
+
+    let start = Instant::now();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    // response is sent, then connection is closed once it becomes idle
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), stream.read_to_end(&mut response))
+        .await
+        .expect("connection should be closed by the server")
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert!(String::from_utf8_lossy(&response).starts_with("HTTP/1.1 200 OK\r\n"));
+
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_shutdown_timeout() {
+    let app = Router::new().route("/forever", get(std::future::pending::<()>));
+    let timeouts = ServerTimeouts {
+        shutdown: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let config = ServerConfig {
+        timeouts,
+        ..local_config().await
+    };
+    let server = RunningServer::start(config, app).await;
+    let mut stream = server.connect().await;
+
+    stream
+        .write_all(b"GET /forever HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    // make sure the request is in flight before shutting down
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let start = Instant::now();
+    tokio::time::timeout(Duration::from_secs(10), server.shutdown())
+        .await
+        .expect("shutdown should complete after shutdown timeout");
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    // the request is cut without a response
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    assert!(response.is_empty());
+}
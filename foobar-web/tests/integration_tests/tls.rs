@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use rustls::pki_types::ServerName;
+use sqlx::PgPool;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_rustls::TlsConnector;
+
+use foobar_common::testing::temp_dir;
+use foobar_web::create_app;
+use foobar_web::server::ServerConfig;
+use foobar_web::tls::TlsConfig;
+
+use super::running_server::{RunningServer, local_config};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_tls(pool: PgPool) {
+    let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let dir = temp_dir();
+    let tls = TlsConfig {
+        cert: dir.path().join("cert.pem"),
+        key: dir.path().join("key.pem"),
+    };
+    std::fs::write(&tls.cert, generated.cert.pem()).unwrap();
+    std::fs::write(&tls.key, generated.signing_key.serialize_pem()).unwrap();
+
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let config = ServerConfig {
+        tls: Some(tls),
+        ..local_config().await
+    };
+    let server = RunningServer::start(config, app).await;
+    let stream = server.connect().await;
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(generated.cert.der().clone()).unwrap();
+    let client_config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    let mut stream = TlsConnector::from(Arc::new(client_config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .expect("TLS handshake should succeed");
+
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    // server may close without close_notify, which is fine for this test
+    let _ = stream.read_to_string(&mut response).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Sample item foo"));
+
+    server.shutdown().await;
+}
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use sqlx::PgPool;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+use foobar_common::testing::temp_dir;
+use foobar_web::create_app;
+use foobar_web::server::{ListenAddr, ServerConfig};
+
+use super::running_server::{RunningServer, local_config};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
+async fn test_unix_socket(pool: PgPool) {
+    let dir = temp_dir();
+    let path = dir.path().join("foobar.sock");
+    let app = create_app(pool, Default::default()).await.unwrap();
+    let config = ServerConfig {
+        listen: ListenAddr::Unix(path.clone()),
+        ..local_config().await
+    };
+    let server = RunningServer::start(config, app).await;
+    let mut stream = UnixStream::connect(&path).await.unwrap();
+
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Sample item foo"));
+
+    server.shutdown().await;
+    assert!(!path.exists());
+}
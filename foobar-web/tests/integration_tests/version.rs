@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use axum_test::TestServer;
+use sqlx::PgPool;
+
+use foobar_web::{Readiness, create_app_with_readiness};
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_version(pool: PgPool) {
+    // available before startup is complete
+    let server = TestServer::new(
+        create_app_with_readiness(pool, Default::default(), Readiness::default())
+            .await
+            .unwrap(),
+    );
+    let response = server.get("/version").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+
+    let json: serde_json::Value = response.json();
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(
+        json["git_hash"]
+            .as_str()
+            .is_some_and(|hash| !hash.is_empty())
+    );
+    assert!(
+        json["build_time"]
+            .as_str()
+            .is_some_and(|time| !time.is_empty())
+    );
+}
@@ -8,6 +8,6 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR")]
 async fn test_about(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     insta::assert_snapshot!(server.get("/about").await);
 }
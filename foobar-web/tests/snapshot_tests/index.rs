@@ -8,6 +8,12 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_index(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
+    insta::assert_snapshot!(server.get("/").await);
+}
+
+#[sqlx::test(migrator = "foobar_common::MIGRATOR")]
+async fn test_index_empty(pool: PgPool) {
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     insta::assert_snapshot!(server.get("/").await);
 }
@@ -8,12 +8,12 @@ use foobar_web::create_app;
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_item(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     insta::assert_snapshot!(server.get("/item/1").await);
 }
 
 #[sqlx::test(migrator = "foobar_common::MIGRATOR", fixtures("sample_items"))]
 async fn test_item_not_found(pool: PgPool) {
-    let server = TestServer::new(create_app(pool).await.unwrap());
+    let server = TestServer::new(create_app(pool, Default::default()).await.unwrap());
     insta::assert_snapshot!(server.get("/item/999").await);
 }
@@ -0,0 +1,7 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+CREATE TABLE worker_runs (
+	worker text PRIMARY KEY,
+	last_success timestamp with time zone NOT NULL
+);
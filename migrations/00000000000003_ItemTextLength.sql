@@ -0,0 +1,5 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- must match foobar_common::MAX_ITEM_TEXT_LENGTH
+ALTER TABLE items ADD CONSTRAINT items_text_length CHECK (char_length(text) <= 1024);
@@ -0,0 +1,13 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- Creating the index fails if there are duplicate texts, so these are
+-- removed first, keeping the oldest (lowest id) item of each text.
+-- NOTE: this irreversibly deletes the duplicate rows; review them with
+-- SELECT text, count(*) FROM items GROUP BY text HAVING count(*) > 1
+-- and back up the table before deploying if they matter.
+DELETE FROM items AS duplicate
+USING items AS original
+WHERE duplicate.text = original.text AND duplicate.id > original.id;
+
+CREATE UNIQUE INDEX items_text_idx ON items(text);
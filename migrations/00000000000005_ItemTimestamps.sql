@@ -0,0 +1,20 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE items
+	ADD COLUMN created_at timestamp with time zone NOT NULL DEFAULT now(),
+	ADD COLUMN updated_at timestamp with time zone NOT NULL DEFAULT now();
+
+UPDATE items SET created_at = time, updated_at = time;
+
+CREATE FUNCTION set_updated_at() RETURNS trigger AS $$
+BEGIN
+	NEW.updated_at := now();
+	RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER items_set_updated_at
+	BEFORE UPDATE ON items
+	FOR EACH ROW
+	EXECUTE FUNCTION set_updated_at();
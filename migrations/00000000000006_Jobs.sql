@@ -0,0 +1,12 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+CREATE TABLE jobs (
+	id bigint GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+	kind text NOT NULL,
+	payload text NOT NULL DEFAULT '',
+	created_at timestamp with time zone NOT NULL DEFAULT now(),
+	completed_at timestamp with time zone
+);
+
+CREATE INDEX jobs_pending_idx ON jobs(id) WHERE completed_at IS NULL;
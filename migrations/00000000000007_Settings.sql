@@ -0,0 +1,8 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+CREATE TABLE settings (
+	key text PRIMARY KEY,
+	value text NOT NULL,
+	updated_at timestamp with time zone NOT NULL DEFAULT now()
+);
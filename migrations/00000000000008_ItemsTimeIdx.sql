@@ -0,0 +1,5 @@
+-- SPDX-FileCopyrightText: Copyright 2026 Dmitry Marakasov <amdmi3@amdmi3.ru>
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- serves keyset pagination of items, see foobar-web cursor module
+CREATE INDEX items_time_id_idx ON items(time, id);